@@ -2,6 +2,7 @@
 
 use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
+use eyre::WrapErr;
 use reth_config::Config;
 use reth_net_nat::NatResolver;
 use reth_network::{HelloMessage, NetworkConfigBuilder};
@@ -37,6 +38,16 @@ pub struct NetworkArgs {
     #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
     pub peers_file: Option<PathBuf>,
 
+    /// The path to the peers access list file, containing `allow` and `deny` rules for node ids
+    /// and IP ranges, one per line:
+    ///
+    /// deny 203.0.113.0/24
+    /// allow 10.0.0.0/8
+    ///
+    /// Rules added via the `admin` RPC namespace are persisted to this file.
+    #[arg(long = "peers.access-list", value_name = "FILE", verbatim_doc_comment)]
+    pub peers_access_list: Option<PathBuf>,
+
     /// Custom node identity
     #[arg(long, value_name = "IDENTITY", default_value = P2P_CLIENT_VERSION)]
     pub identity: String,
@@ -76,22 +87,30 @@ impl NetworkArgs {
     ///
     /// The `default_peers_file` will be used as the default location to store the persistent peers
     /// file if `no_persist_peers` is false, and there is no provided `peers_file`.
+    ///
+    /// Returns an error if the peers access list file can't be read.
     pub fn network_config(
         &self,
         config: &Config,
         chain_spec: Arc<ChainSpec>,
         secret_key: SecretKey,
         default_peers_file: PathBuf,
-    ) -> NetworkConfigBuilder {
+    ) -> eyre::Result<NetworkConfigBuilder> {
         let chain_bootnodes = chain_spec.chain.bootnodes().unwrap_or_else(mainnet_nodes);
         let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
 
         // Configure peer connections
-        let peer_config = config
+        let mut peer_config = config
             .peers
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
+        if let Some(access_list_file) = &self.peers_access_list {
+            peer_config =
+                peer_config.with_access_list_file(access_list_file).wrap_err_with(|| {
+                    format!("failed to load peers access list {}", access_list_file.display())
+                })?;
+        }
 
         // Configure basic network stack
         let mut network_config_builder = config
@@ -105,7 +124,7 @@ impl NetworkArgs {
         network_config_builder = network_config_builder
             .hello_message(HelloMessage::builder(peer_id).client_version(&self.identity).build());
 
-        self.discovery.apply_to_builder(network_config_builder)
+        Ok(self.discovery.apply_to_builder(network_config_builder))
     }
 
     /// If `no_persist_peers` is true then this returns the path to the persistent peers file path.
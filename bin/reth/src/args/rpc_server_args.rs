@@ -12,7 +12,7 @@ use futures::TryFutureExt;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
-    HeaderProvider, StateProviderFactory,
+    HeaderProvider, PruneCheckpointReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -20,16 +20,22 @@ use reth_rpc::{
             DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_ENV_CACHE_MAX_LEN, DEFAULT_RECEIPT_CACHE_MAX_LEN,
         },
         gas_oracle::GasPriceOracleConfig,
-        RPC_DEFAULT_GAS_CAP,
+        CallLimits, RPC_DEFAULT_GAS_CAP, RPC_DEFAULT_MEMORY_LIMIT,
     },
-    JwtError, JwtSecret,
+    explorer::{DEFAULT_EXPLORER_MAX_ADDRESSES, DEFAULT_EXPLORER_MAX_TRANSFERS_PER_ADDRESS},
+    trace_cache::{
+        TraceCacheConfig, DEFAULT_TRACE_CACHE_MAX_BLOCK_AGE, DEFAULT_TRACE_CACHE_MAX_BYTES,
+        DEFAULT_TRACE_CACHE_MAX_ENTRIES,
+    },
+    ExplorerConfig, JwtError, JwtSecret,
 };
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
     constants,
     error::RpcError,
     EthConfig, IpcServerBuilder, RethRpcModule, RpcModuleBuilder, RpcModuleConfig,
-    RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
+    RpcModuleSelection, RpcServerConfig, RpcServerHandle, ServerBuilder, TransportCallLimits,
+    TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::{EngineApi, EngineApiServer};
 use reth_tasks::TaskSpawner;
@@ -150,6 +156,62 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum code size in bytes of state overrides in `eth_call` and call tracing RPC methods.
+    #[arg(long, value_name = "BYTES")]
+    pub rpc_max_code_size: Option<usize>,
+
+    /// Maximum EVM memory in bytes for `eth_call` and call tracing RPC methods.
+    #[arg(long, value_name = "BYTES", default_value_t = RPC_DEFAULT_MEMORY_LIMIT)]
+    pub rpc_memory_limit: u64,
+
+    /// Lift the code size and memory limits of `eth_call` and call tracing RPC methods on local
+    /// transports. The gas cap still applies.
+    ///
+    /// Local transports are IPC, the auth server and HTTP/WS servers that listen on a loopback
+    /// address. The limits are lifted per transport, not per caller, so this is unsafe if a
+    /// loopback HTTP/WS server is exposed through a reverse proxy: every proxied request is
+    /// unlimited as well.
+    #[arg(long)]
+    pub rpc_unlimited_local_calls: bool,
+
+    /// Directory for the disk-backed cache of block traces.
+    ///
+    /// If set, the results of `debug_traceBlockByNumber`, `debug_traceBlockByHash` and
+    /// `trace_block` for recent blocks are cached in this directory.
+    #[arg(long, value_name = "PATH")]
+    pub rpc_trace_cache_dir: Option<PathBuf>,
+
+    /// Maximum number of block traces in the trace cache.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_TRACE_CACHE_MAX_ENTRIES)]
+    pub rpc_trace_cache_max_entries: u32,
+
+    /// Maximum number of blocks a block can be behind the tip for its traces to be cached.
+    #[arg(long, value_name = "BLOCKS", default_value_t = DEFAULT_TRACE_CACHE_MAX_BLOCK_AGE)]
+    pub rpc_trace_cache_max_block_age: u64,
+
+    /// Maximum total size in bytes of all block traces in the trace cache.
+    ///
+    /// The traces of a block that exceed this size on their own are not cached.
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_TRACE_CACHE_MAX_BYTES)]
+    pub rpc_trace_cache_max_bytes: u64,
+
+    /// Number of blocks up to the tip that the `explorer` namespace indexes on startup.
+    ///
+    /// The namespace only serves aggregates of indexed blocks, which are the backfilled blocks and
+    /// all blocks imported afterwards.
+    #[arg(long, value_name = "BLOCKS", default_value_t = 0)]
+    pub rpc_explorer_backfill_blocks: u64,
+
+    /// Maximum number of token transfers the `explorer` namespace keeps per address.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_EXPLORER_MAX_TRANSFERS_PER_ADDRESS)]
+    pub rpc_explorer_max_transfers: usize,
+
+    /// Maximum number of addresses the `explorer` namespace keeps in its index.
+    ///
+    /// Once exceeded, the aggregates of the least recently updated address are dropped.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_EXPLORER_MAX_ADDRESSES)]
+    pub rpc_explorer_max_addresses: u32,
+
     /// Gas price oracle configuration.
     #[clap(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
@@ -192,6 +254,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -258,6 +321,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -294,6 +358,7 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + HeaderProvider
             + StateProviderFactory
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -327,6 +392,10 @@ impl RethRpcConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_logs_per_response(self.rpc_max_logs_per_response)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .rpc_max_code_size(self.rpc_max_code_size)
+            .rpc_memory_limit(Some(self.rpc_memory_limit))
+            .transport_call_limits(self.transport_call_limits())
+            .trace_cache(self.trace_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
 
@@ -347,9 +416,44 @@ impl RethRpcConfig for RpcServerArgs {
         )
     }
 
+    fn trace_cache_config(&self) -> Option<TraceCacheConfig> {
+        let dir = self.rpc_trace_cache_dir.as_ref()?;
+        Some(
+            TraceCacheConfig::new(dir)
+                .max_entries(self.rpc_trace_cache_max_entries)
+                .max_block_age(self.rpc_trace_cache_max_block_age)
+                .max_bytes(self.rpc_trace_cache_max_bytes),
+        )
+    }
+
+    fn explorer_config(&self) -> ExplorerConfig {
+        ExplorerConfig::default()
+            .backfill_blocks(self.rpc_explorer_backfill_blocks)
+            .max_transfers_per_address(self.rpc_explorer_max_transfers)
+            .max_addresses(self.rpc_explorer_max_addresses)
+    }
+
+    fn transport_call_limits(&self) -> TransportCallLimits {
+        let mut limits = TransportCallLimits::default();
+        if !self.rpc_unlimited_local_calls {
+            return limits
+        }
+
+        let trusted = CallLimits::trusted(self.rpc_gas_cap);
+        limits = limits.with_ipc(trusted).with_auth(trusted);
+        if self.http_addr.is_loopback() {
+            limits = limits.with_http(trusted);
+        }
+        if self.ws_addr.is_loopback() {
+            limits = limits.with_ws(trusted);
+        }
+        limits
+    }
+
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default()
-            .with_config(RpcModuleConfig::new(self.eth_config()));
+        let mut config = TransportRpcModuleConfig::default().with_config(
+            RpcModuleConfig::new(self.eth_config()).with_explorer(self.explorer_config()),
+        );
 
         if self.http {
             config = config.with_http(
@@ -500,6 +604,95 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_call_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config();
+        assert_eq!(config.rpc_max_code_size, None);
+        assert_eq!(config.rpc_memory_limit, Some(RPC_DEFAULT_MEMORY_LIMIT));
+        assert_eq!(config.transport_call_limits, TransportCallLimits::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-max-code-size",
+            "24576",
+            "--rpc-memory-limit",
+            "1048576",
+            "--rpc-unlimited-local-calls",
+            "--ws.addr",
+            "0.0.0.0",
+        ])
+        .args;
+        let config = args.eth_config();
+        assert_eq!(
+            config.call_limits(),
+            CallLimits::new(RPC_DEFAULT_GAS_CAP.into())
+                .with_max_code_size(Some(24576))
+                .with_memory_limit(Some(1048576))
+        );
+        let limits = config.transport_call_limits;
+        let trusted = CallLimits::trusted(RPC_DEFAULT_GAS_CAP.into());
+        assert_eq!(limits.http, Some(trusted));
+        assert_eq!(limits.ipc, Some(trusted));
+        assert_eq!(limits.auth, Some(trusted));
+        assert_eq!(limits.ws, None);
+
+        // the configured gas cap still applies to local transports
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.gascap",
+            "1000",
+            "--rpc-unlimited-local-calls",
+        ])
+        .args;
+        assert_eq!(args.eth_config().transport_call_limits.ipc, Some(CallLimits::trusted(1000)));
+    }
+
+    #[test]
+    fn test_rpc_trace_cache() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().trace_cache, None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-trace-cache-dir",
+            "/tmp/traces",
+            "--rpc-trace-cache-max-entries",
+            "10",
+            "--rpc-trace-cache-max-bytes",
+            "1000000",
+        ])
+        .args;
+        assert_eq!(
+            args.eth_config().trace_cache,
+            Some(TraceCacheConfig::new("/tmp/traces").max_entries(10).max_bytes(1_000_000))
+        );
+    }
+
+    #[test]
+    fn test_rpc_explorer_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.explorer_config(), ExplorerConfig::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-explorer-backfill-blocks",
+            "1000",
+            "--rpc-explorer-max-transfers",
+            "10",
+            "--rpc-explorer-max-addresses",
+            "500",
+        ])
+        .args;
+        assert_eq!(
+            args.explorer_config(),
+            ExplorerConfig::default()
+                .backfill_blocks(1000)
+                .max_transfers_per_address(10)
+                .max_addresses(500)
+        );
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
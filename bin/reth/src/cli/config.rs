@@ -2,10 +2,13 @@
 
 use reth_revm::primitives::bytes::BytesMut;
 use reth_rlp::Encodable;
-use reth_rpc::{eth::gas_oracle::GasPriceOracleConfig, JwtError, JwtSecret};
+use reth_rpc::{
+    eth::gas_oracle::GasPriceOracleConfig, trace_cache::TraceCacheConfig, ExplorerConfig, JwtError,
+    JwtSecret,
+};
 use reth_rpc_builder::{
     auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcServerConfig,
-    ServerBuilder, TransportRpcModuleConfig,
+    ServerBuilder, TransportCallLimits, TransportRpcModuleConfig,
 };
 use std::{borrow::Cow, path::PathBuf, time::Duration};
 
@@ -29,6 +32,17 @@ pub trait RethRpcConfig {
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
+    /// Extracts the trace cache config from the args, if the cache is enabled.
+    fn trace_cache_config(&self) -> Option<TraceCacheConfig>;
+
+    /// Extracts the explorer namespace config from the args.
+    fn explorer_config(&self) -> ExplorerConfig;
+
+    /// Returns the per transport overrides of the `eth_call` limits.
+    ///
+    /// This lifts the limits for local origins if configured.
+    fn transport_call_limits(&self) -> TransportCallLimits;
+
     /// Creates the [TransportRpcModuleConfig] from cli args.
     ///
     /// This sets all the api modules, and configures additional settings like gas price oracle
@@ -8,7 +8,7 @@ use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_primitives::ChainSpec;
 use reth_provider::{
     BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
-    StateProviderFactory,
+    PruneCheckpointReader, StateProviderFactory,
 };
 use reth_rpc_builder::{RethModuleRegistry, TransportRpcModules};
 use reth_tasks::TaskSpawner;
@@ -53,6 +53,7 @@ pub trait RethNodeCommandConfig: fmt::Debug {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -177,6 +178,7 @@ impl<T: RethNodeCommandConfig> RethNodeCommandConfig for NoArgs<T> {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
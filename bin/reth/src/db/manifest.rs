@@ -0,0 +1,81 @@
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use reth_nippy_jar::Manifest;
+use reth_primitives::fs;
+use secp256k1::{PublicKey, SecretKey};
+use std::path::PathBuf;
+
+/// The arguments for the `reth db manifest` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+/// `reth db manifest` subcommands
+pub enum Subcommands {
+    /// Generates the manifest of a directory of static files, replacing an existing one.
+    ///
+    /// Once a directory has a manifest, it is updated whenever a static file of the directory is
+    /// written or deleted. Such an update drops the signature.
+    Generate {
+        /// The directory of static files
+        dir: PathBuf,
+        /// Signs the manifest with the hex encoded secret key stored in the given file
+        #[arg(long, value_name = "PATH")]
+        sign: Option<PathBuf>,
+    },
+    /// Verifies the size and hash of every file listed in the manifest of a directory
+    Verify {
+        /// The directory of static files
+        dir: PathBuf,
+        /// Also requires the manifest to be signed by the given hex encoded public key
+        #[arg(long, value_name = "PUBLIC_KEY")]
+        trusted_key: Option<PublicKey>,
+    },
+}
+
+impl Command {
+    /// Execute `db manifest` command
+    pub fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Generate { dir, sign } => {
+                // block ranges can't be derived from the files, so they're kept from an existing
+                // manifest
+                let existing = Manifest::load(&dir).ok();
+                let mut manifest = Manifest::generate(&dir, |path| {
+                    existing.as_ref()?.entry(path)?.block_range.clone()
+                })?;
+
+                if let Some(path) = sign {
+                    let secret_key = fs::read_to_string(&path)?
+                        .trim()
+                        .parse::<SecretKey>()
+                        .wrap_err_with(|| format!("Invalid secret key in {}", path.display()))?;
+                    manifest.sign(&secret_key);
+                }
+
+                manifest.save(&dir)?;
+                println!(
+                    "Generated manifest of {} files in {}",
+                    manifest.entries.len(),
+                    dir.display()
+                );
+            }
+            Subcommands::Verify { dir, trusted_key } => {
+                let manifest = Manifest::load(&dir)
+                    .wrap_err_with(|| format!("Failed to load manifest of {}", dir.display()))?;
+
+                if let Some(trusted_key) = trusted_key {
+                    manifest.verify_signature(&trusted_key)?;
+                }
+                manifest.verify(&dir)?;
+
+                println!("Verified {} files in {}", manifest.entries.len(), dir.display());
+            }
+        }
+
+        Ok(())
+    }
+}
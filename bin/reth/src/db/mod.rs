@@ -24,6 +24,7 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod manifest;
 /// DB List TUI
 mod tui;
 
@@ -88,6 +89,8 @@ pub enum Subcommands {
     Version,
     /// Returns the full database path
     Path,
+    /// Generates or verifies the manifest of a directory of static files
+    Manifest(manifest::Command),
 }
 
 impl Command {
@@ -227,6 +230,9 @@ impl Command {
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            Subcommands::Manifest(command) => {
+                command.execute()?;
+            }
         }
 
         Ok(())
@@ -243,4 +249,23 @@ mod tests {
         let cmd = Command::try_parse_from(["reth", "stats", "--datadir", "../mainnet"]).unwrap();
         assert_eq!(cmd.datadir.as_ref(), Some(Path::new("../mainnet")));
     }
+
+    #[test]
+    fn parse_manifest() {
+        let cmd = Command::try_parse_from(["reth", "manifest", "verify", "snapshots"]).unwrap();
+        assert!(matches!(cmd.command, Subcommands::Manifest(_)));
+
+        let key = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        Command::try_parse_from(["reth", "manifest", "verify", "snapshots", "--trusted-key", key])
+            .unwrap();
+        assert!(Command::try_parse_from([
+            "reth",
+            "manifest",
+            "verify",
+            "snapshots",
+            "--trusted-key",
+            "0x00"
+        ])
+        .is_err());
+    }
 }
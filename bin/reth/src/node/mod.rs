@@ -341,7 +341,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
             head,
             secret_key,
             default_peers_path.clone(),
-        );
+        )?;
         let network = self
             .start_network(
                 network_config,
@@ -755,9 +755,10 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
         head: Head,
         secret_key: SecretKey,
         default_peers_path: PathBuf,
-    ) -> NetworkConfig<ProviderFactory<Arc<DatabaseEnv>>> {
-        self.network
-            .network_config(config, self.chain.clone(), secret_key, default_peers_path)
+    ) -> eyre::Result<NetworkConfig<ProviderFactory<Arc<DatabaseEnv>>>> {
+        Ok(self
+            .network
+            .network_config(config, self.chain.clone(), secret_key, default_peers_path)?
             .with_task_executor(Box::new(executor))
             .set_head(head)
             .listener_addr(SocketAddr::V4(SocketAddrV4::new(
@@ -776,7 +777,7 @@ impl<Ext: RethCliExt> NodeCommand<Ext> {
                     None => DEFAULT_DISCOVERY_PORT + self.instance - 1,
                 },
             )))
-            .build(ProviderFactory::new(db, self.chain.clone()))
+            .build(ProviderFactory::new(db, self.chain.clone())))
     }
 
     #[allow(clippy::too_many_arguments)]
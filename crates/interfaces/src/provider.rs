@@ -1,4 +1,6 @@
-use reth_primitives::{Address, BlockHash, BlockHashOrNumber, BlockNumber, TxNumber, H256};
+use reth_primitives::{
+    Address, BlockHash, BlockHashOrNumber, BlockNumber, PrunePart, TxNumber, H256,
+};
 
 /// Bundled errors variants thrown by various providers.
 #[allow(missing_docs)]
@@ -94,6 +96,15 @@ pub enum ProviderError {
         /// Block hash
         block_hash: BlockHash,
     },
-    #[error("State at block #{0} is pruned")]
-    StateAtBlockPruned(BlockNumber),
+    /// Thrown when the requested data is no longer available locally, e.g. because it was pruned.
+    #[error("{part} at block #{block_number} is unavailable, lowest available block is #{lowest_available}")]
+    HistoryUnavailable {
+        /// The part of the history that is unavailable
+        part: PrunePart,
+        /// The requested block number. For account and storage history this is the block at the
+        /// start of which the state was requested.
+        block_number: BlockNumber,
+        /// The lowest block number at which the part is available
+        lowest_available: BlockNumber,
+    },
 }
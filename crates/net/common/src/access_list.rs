@@ -0,0 +1,556 @@
+//! Operator managed allow and deny lists for peers.
+//!
+//! Unlike the [`BanList`](crate::ban_list::BanList), which is maintained by the node itself based
+//! on peer behaviour, the [`PeerAccessList`] is only modified by the operator, e.g. via a file or
+//! the admin RPC, and is never evicted.
+//!
+//! The list can be exported to and imported from a simple line based format, so the same list
+//! can be distributed to multiple nodes:
+//!
+//! ```text
+//! # block a whole range
+//! deny 203.0.113.0/24
+//! # block a single node id
+//! deny 6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0
+//! allow 10.0.0.0/8
+//! ```
+
+use reth_primitives::{fs, hex, PeerId};
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{self, Write},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A range of IP addresses in CIDR notation, e.g. `10.0.0.0/8`.
+///
+/// A single address without prefix length is treated as a range that only contains that address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IpCidr {
+    /// The network address, all bits outside the prefix are zero.
+    addr: IpAddr,
+    /// The number of leading bits that are fixed.
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Creates a new range from the given address and prefix length.
+    ///
+    /// Bits of the address outside the prefix are cleared.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, AccessListError> {
+        let max_len = max_prefix_len(&addr);
+        if prefix_len > max_len {
+            return Err(AccessListError::InvalidPrefixLength(prefix_len))
+        }
+        let addr = match addr {
+            IpAddr::V4(ip) => {
+                let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or_default();
+                IpAddr::from((u32::from(ip) & mask).to_be_bytes())
+            }
+            IpAddr::V6(ip) => {
+                let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or_default();
+                IpAddr::from((u128::from(ip) & mask).to_be_bytes())
+            }
+        };
+        Ok(Self { addr, prefix_len })
+    }
+
+    /// Returns the network address of the range.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length of the range.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns true if the given address is part of this range.
+    ///
+    /// IPv4-mapped IPv6 addresses are matched against IPv4 ranges.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(*ip),
+            ip => *ip,
+        };
+        match (self.addr, ip) {
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                Self::new(ip, self.prefix_len).map(|other| other.addr == self.addr).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<IpAddr> for IpCidr {
+    fn from(addr: IpAddr) -> Self {
+        Self { addr, prefix_len: max_prefix_len(&addr) }
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = AccessListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AccessListError::InvalidTarget(s.to_string());
+        match s.split_once('/') {
+            Some((addr, prefix_len)) => {
+                let addr = addr.parse::<IpAddr>().map_err(|_| invalid())?;
+                let prefix_len = prefix_len.parse::<u8>().map_err(|_| invalid())?;
+                Self::new(addr, prefix_len)
+            }
+            None => Ok(s.parse::<IpAddr>().map_err(|_| invalid())?.into()),
+        }
+    }
+}
+
+/// Returns the number of bits of the address.
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// What an access rule matches on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessTarget {
+    /// A single node id.
+    Peer(PeerId),
+    /// A range of IP addresses.
+    Range(IpCidr),
+}
+
+impl fmt::Display for AccessTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessTarget::Peer(peer_id) => f.write_str(&hex::encode(peer_id.as_bytes())),
+            AccessTarget::Range(range) => range.fmt(f),
+        }
+    }
+}
+
+impl FromStr for AccessTarget {
+    type Err = AccessListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // node ids are 64 bytes hex encoded, everything else must be an address or range
+        let id = s.strip_prefix("0x").unwrap_or(s);
+        if id.len() == 128 {
+            return PeerId::from_str(id)
+                .map(AccessTarget::Peer)
+                .map_err(|_| AccessListError::InvalidTarget(s.to_string()))
+        }
+        s.parse().map(AccessTarget::Range)
+    }
+}
+
+/// Whether a rule permits or rejects connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessAction {
+    /// Only matching peers are allowed to connect, once any allow rule exists.
+    Allow,
+    /// Matching peers are never connected to.
+    Deny,
+}
+
+impl fmt::Display for AccessAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessAction::Allow => f.write_str("allow"),
+            AccessAction::Deny => f.write_str("deny"),
+        }
+    }
+}
+
+impl FromStr for AccessAction {
+    type Err = AccessListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(AccessAction::Allow),
+            "deny" => Ok(AccessAction::Deny),
+            _ => Err(AccessListError::InvalidAction(s.to_string())),
+        }
+    }
+}
+
+/// A single entry of the [`PeerAccessList`], e.g. `deny 203.0.113.0/24`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccessRule {
+    /// Whether the rule allows or denies the target.
+    pub action: AccessAction,
+    /// What the rule matches on.
+    pub target: AccessTarget,
+}
+
+impl AccessRule {
+    /// Creates a rule that allows the given target.
+    pub fn allow(target: AccessTarget) -> Self {
+        Self { action: AccessAction::Allow, target }
+    }
+
+    /// Creates a rule that denies the given target.
+    pub fn deny(target: AccessTarget) -> Self {
+        Self { action: AccessAction::Deny, target }
+    }
+}
+
+impl fmt::Display for AccessRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.action, self.target)
+    }
+}
+
+impl FromStr for AccessRule {
+    type Err = AccessListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let (Some(action), Some(target), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(AccessListError::InvalidRule(s.to_string()))
+        };
+        Ok(Self { action: action.parse()?, target: target.parse()? })
+    }
+}
+
+/// Operator managed allow and deny lists for node ids and IP ranges.
+///
+/// A peer is rejected if it matches any deny rule. If at least one allow rule exists, a peer is
+/// also rejected unless it matches an allow rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerAccessList {
+    denied_peers: HashSet<PeerId>,
+    denied_ranges: HashSet<IpCidr>,
+    allowed_peers: HashSet<PeerId>,
+    allowed_ranges: HashSet<IpCidr>,
+}
+
+impl PeerAccessList {
+    /// Creates a new list from the given rules.
+    pub fn new(rules: impl IntoIterator<Item = AccessRule>) -> Self {
+        let mut list = Self::default();
+        for rule in rules {
+            list.add_rule(rule);
+        }
+        list
+    }
+
+    /// Reads the list from the given file.
+    ///
+    /// Returns an empty list if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                content.parse().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the list to the given file, replacing its content.
+    ///
+    /// The list is written to a temporary file next to the target first, which is then renamed
+    /// over the target, so the file is never left truncated.
+    ///
+    /// Only the rules are written, in the order of [`Self::rules`]: comments and the order of
+    /// the file the list was loaded from are not preserved.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(self.to_string().as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, path).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    }
+
+    /// Returns true if the list contains no rules.
+    pub fn is_empty(&self) -> bool {
+        self.denied_peers.is_empty() &&
+            self.denied_ranges.is_empty() &&
+            self.allowed_peers.is_empty() &&
+            self.allowed_ranges.is_empty()
+    }
+
+    /// Returns true if the list contains any allow rule.
+    pub fn has_allow_rules(&self) -> bool {
+        !self.allowed_peers.is_empty() || !self.allowed_ranges.is_empty()
+    }
+
+    /// Adds the rule to the list.
+    ///
+    /// Returns false if the rule was already present.
+    pub fn add_rule(&mut self, rule: AccessRule) -> bool {
+        match (rule.action, rule.target) {
+            (AccessAction::Allow, AccessTarget::Peer(peer_id)) => {
+                self.allowed_peers.insert(peer_id)
+            }
+            (AccessAction::Allow, AccessTarget::Range(range)) => self.allowed_ranges.insert(range),
+            (AccessAction::Deny, AccessTarget::Peer(peer_id)) => self.denied_peers.insert(peer_id),
+            (AccessAction::Deny, AccessTarget::Range(range)) => self.denied_ranges.insert(range),
+        }
+    }
+
+    /// Removes the rule from the list.
+    ///
+    /// Returns false if the rule was not present.
+    pub fn remove_rule(&mut self, rule: &AccessRule) -> bool {
+        match (rule.action, &rule.target) {
+            (AccessAction::Allow, AccessTarget::Peer(peer_id)) => {
+                self.allowed_peers.remove(peer_id)
+            }
+            (AccessAction::Allow, AccessTarget::Range(range)) => self.allowed_ranges.remove(range),
+            (AccessAction::Deny, AccessTarget::Peer(peer_id)) => self.denied_peers.remove(peer_id),
+            (AccessAction::Deny, AccessTarget::Range(range)) => self.denied_ranges.remove(range),
+        }
+    }
+
+    /// Returns all rules of the list, deny rules first.
+    ///
+    /// Within each group, the rules are sorted, so the output is deterministic.
+    pub fn rules(&self) -> Vec<AccessRule> {
+        let ranges = |ranges: &HashSet<IpCidr>, action| {
+            let mut ranges = ranges.iter().copied().collect::<Vec<_>>();
+            ranges.sort_unstable();
+            ranges
+                .into_iter()
+                .map(move |range| AccessRule { action, target: AccessTarget::Range(range) })
+        };
+        let peers = |peers: &HashSet<PeerId>, action| {
+            let mut peers = peers.iter().copied().collect::<Vec<_>>();
+            peers.sort_unstable();
+            peers
+                .into_iter()
+                .map(move |peer_id| AccessRule { action, target: AccessTarget::Peer(peer_id) })
+        };
+
+        let mut rules = ranges(&self.denied_ranges, AccessAction::Deny).collect::<Vec<_>>();
+        rules.extend(peers(&self.denied_peers, AccessAction::Deny));
+        rules.extend(ranges(&self.allowed_ranges, AccessAction::Allow));
+        rules.extend(peers(&self.allowed_peers, AccessAction::Allow));
+        rules
+    }
+
+    /// Returns true if a connection from or to the given IP address may be attempted.
+    ///
+    /// This is used if the node id is not known yet, e.g. when accepting a tcp connection. If
+    /// node ids are on the allow list, the address can only be rejected once the node id is known,
+    /// see [`Self::is_permitted`].
+    pub fn is_ip_permitted(&self, ip: &IpAddr) -> bool {
+        if self.denied_ranges.iter().any(|range| range.contains(ip)) {
+            return false
+        }
+        if self.allowed_peers.is_empty() && !self.allowed_ranges.is_empty() {
+            return self.allowed_ranges.iter().any(|range| range.contains(ip))
+        }
+        true
+    }
+
+    /// Returns true if the peer with the given id and IP address may be connected to.
+    pub fn is_permitted(&self, peer_id: &PeerId, ip: &IpAddr) -> bool {
+        if self.denied_peers.contains(peer_id) ||
+            self.denied_ranges.iter().any(|range| range.contains(ip))
+        {
+            return false
+        }
+        if self.has_allow_rules() {
+            return self.allowed_peers.contains(peer_id) ||
+                self.allowed_ranges.iter().any(|range| range.contains(ip))
+        }
+        true
+    }
+}
+
+/// Formats the list as one rule per line, see [`PeerAccessList::rules`].
+impl fmt::Display for PeerAccessList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in self.rules() {
+            writeln!(f, "{rule}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PeerAccessList {
+    type Err = AccessListError;
+
+    /// Parses the list from one rule per line, empty lines and `#` comments are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut list = Self::default();
+        for line in s.lines() {
+            let line = line.split_once('#').map(|(rule, _)| rule).unwrap_or(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            list.add_rule(line.parse()?);
+        }
+        Ok(list)
+    }
+}
+
+/// Errors that can occur when parsing a [`PeerAccessList`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AccessListError {
+    /// The rule is not of the form `<allow|deny> <target>`.
+    #[error("invalid access rule: {0}")]
+    InvalidRule(String),
+    /// The action is neither `allow` nor `deny`.
+    #[error("invalid access action: {0}")]
+    InvalidAction(String),
+    /// The target is neither a node id nor an IP address or range.
+    #[error("invalid access target: {0}")]
+    InvalidTarget(String),
+    /// The prefix length exceeds the number of bits of the address.
+    #[error("invalid prefix length: {0}")]
+    InvalidPrefixLength(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cidr_contains() {
+        let range: IpCidr = "10.1.2.3/16".parse().unwrap();
+        assert_eq!(range.to_string(), "10.1.0.0/16");
+        assert!(range.contains(&IpAddr::from([10, 1, 255, 1])));
+        assert!(!range.contains(&IpAddr::from([10, 2, 0, 1])));
+        assert!(range.contains(&"::ffff:10.1.0.1".parse().unwrap()));
+
+        let single: IpCidr = "1.1.1.1".parse().unwrap();
+        assert_eq!(single.prefix_len(), 32);
+        assert!(single.contains(&IpAddr::from([1, 1, 1, 1])));
+        assert!(!single.contains(&IpAddr::from([1, 1, 1, 2])));
+
+        let all: IpCidr = "0.0.0.0/0".parse().unwrap();
+        assert!(all.contains(&IpAddr::from([8, 8, 8, 8])));
+        assert!(!all.contains(&"2001:db8::1".parse().unwrap()));
+
+        let v6: IpCidr = "2001:db8::/32".parse().unwrap();
+        assert!(v6.contains(&"2001:db8:ffff::1".parse().unwrap()));
+        assert!(!v6.contains(&"2001:db9::1".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+        assert!("10.0.0/8".parse::<IpCidr>().is_err());
+    }
+
+    #[test]
+    fn deny_rules() {
+        let peer = PeerId::random();
+        let list = PeerAccessList::new([
+            AccessRule::deny(AccessTarget::Peer(peer)),
+            AccessRule::deny(AccessTarget::Range("203.0.113.0/24".parse().unwrap())),
+        ]);
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let denied_ip = IpAddr::from([203, 0, 113, 7]);
+
+        assert!(!list.is_permitted(&peer, &ip));
+        assert!(!list.is_ip_permitted(&denied_ip));
+        assert!(!list.is_permitted(&PeerId::random(), &denied_ip));
+        assert!(list.is_ip_permitted(&ip));
+        assert!(list.is_permitted(&PeerId::random(), &ip));
+    }
+
+    #[test]
+    fn allow_rules() {
+        let peer = PeerId::random();
+        let mut list = PeerAccessList::new([AccessRule::allow(AccessTarget::Range(
+            "10.0.0.0/8".parse().unwrap(),
+        ))]);
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let allowed_ip = IpAddr::from([10, 0, 0, 1]);
+
+        assert!(list.is_ip_permitted(&allowed_ip));
+        assert!(!list.is_ip_permitted(&ip));
+        assert!(!list.is_permitted(&peer, &ip));
+
+        // once node ids are allowed, ips can only be rejected after the handshake
+        list.add_rule(AccessRule::allow(AccessTarget::Peer(peer)));
+        assert!(list.is_ip_permitted(&ip));
+        assert!(list.is_permitted(&peer, &ip));
+        assert!(!list.is_permitted(&PeerId::random(), &ip));
+        assert!(list.is_permitted(&PeerId::random(), &allowed_ip));
+
+        // deny takes precedence
+        list.add_rule(AccessRule::deny(AccessTarget::Peer(peer)));
+        assert!(!list.is_permitted(&peer, &allowed_ip));
+
+        assert!(list.remove_rule(&AccessRule::deny(AccessTarget::Peer(peer))));
+        assert!(list.is_permitted(&peer, &ip));
+    }
+
+    #[test]
+    fn parse_and_display_list() {
+        let s = r#"
+# known spammers
+deny 203.0.113.0/24
+deny 6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0 # some node
+
+allow 10.0.0.0/8
+"#;
+        let list: PeerAccessList = s.parse().unwrap();
+        assert_eq!(list.rules().len(), 3);
+        assert!(list.has_allow_rules());
+
+        let exported = list.to_string();
+        assert_eq!(exported.parse::<PeerAccessList>().unwrap(), list);
+
+        // rules are exported sorted, regardless of the order they were added in
+        let reversed = PeerAccessList::new(list.rules().into_iter().rev());
+        assert_eq!(reversed.to_string(), exported);
+        assert_eq!(
+            PeerAccessList::new([
+                AccessRule::deny(AccessTarget::Range("10.0.0.2".parse().unwrap())),
+                AccessRule::deny(AccessTarget::Range("10.0.0.1".parse().unwrap())),
+            ])
+            .to_string(),
+            "deny 10.0.0.1/32\ndeny 10.0.0.2/32\n"
+        );
+
+        assert!("block 1.1.1.1".parse::<PeerAccessList>().is_err());
+        assert!("deny".parse::<PeerAccessList>().is_err());
+        assert!("deny 1.1.1.1 extra".parse::<PeerAccessList>().is_err());
+    }
+
+    #[test]
+    fn save_and_load_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("access-list");
+        assert_eq!(PeerAccessList::load(&path).unwrap(), PeerAccessList::default());
+
+        let list = PeerAccessList::new([
+            AccessRule::deny(AccessTarget::Range("203.0.113.0/24".parse().unwrap())),
+            AccessRule::allow(AccessTarget::Peer(PeerId::random())),
+        ]);
+        list.save(&path).unwrap();
+        assert_eq!(PeerAccessList::load(&path).unwrap(), list);
+
+        // saving again replaces the file, without leaving the temporary file behind
+        PeerAccessList::default().save(&path).unwrap();
+        assert_eq!(PeerAccessList::load(&path).unwrap(), PeerAccessList::default());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        std::fs::write(&path, "deny 203.0.11").unwrap();
+        assert_eq!(PeerAccessList::load(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
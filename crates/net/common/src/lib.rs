@@ -9,6 +9,7 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod access_list;
 pub mod ban_list;
 pub mod bandwidth_meter;
 /// Traits related to tokio streams
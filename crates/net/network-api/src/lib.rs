@@ -24,6 +24,7 @@ use std::{net::SocketAddr, sync::Arc};
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
 use reth_eth_wire::capability::Capabilities;
+pub use reth_net_common::access_list::{AccessRule, PeerAccessList};
 
 /// Network Error
 pub mod error;
@@ -96,6 +97,28 @@ pub trait Peers: PeersInfo {
 
     /// Get the reputation of a peer.
     async fn reputation_by_id(&self, peer_id: PeerId) -> Result<Option<Reputation>, NetworkError>;
+
+    /// Adds a rule to the operator managed access list.
+    ///
+    /// Known peers that are denied by the updated list are disconnected and removed.
+    ///
+    /// Returns false if the rule was already present.
+    async fn add_access_rule(&self, rule: AccessRule) -> Result<bool, NetworkError>;
+
+    /// Removes a rule from the operator managed access list.
+    ///
+    /// Returns false if the rule was not present.
+    async fn remove_access_rule(&self, rule: AccessRule) -> Result<bool, NetworkError>;
+
+    /// Replaces the operator managed access list.
+    ///
+    /// Known peers that are denied by the new list are disconnected and removed.
+    ///
+    /// Returns false if the list is unchanged.
+    async fn set_access_list(&self, access_list: PeerAccessList) -> Result<bool, NetworkError>;
+
+    /// Returns the operator managed access list.
+    async fn access_list(&self) -> Result<PeerAccessList, NetworkError>;
 }
 
 /// Represents the kind of peer
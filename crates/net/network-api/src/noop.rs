@@ -4,8 +4,8 @@
 //! generic over it.
 
 use crate::{
-    NetworkError, NetworkInfo, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
-    ReputationChangeKind,
+    AccessRule, NetworkError, NetworkInfo, PeerAccessList, PeerInfo, PeerKind, Peers, PeersInfo,
+    Reputation, ReputationChangeKind,
 };
 use async_trait::async_trait;
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
@@ -82,4 +82,20 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn add_access_rule(&self, _rule: AccessRule) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn remove_access_rule(&self, _rule: AccessRule) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn set_access_list(&self, _access_list: PeerAccessList) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn access_list(&self) -> Result<PeerAccessList, NetworkError> {
+        Ok(PeerAccessList::default())
+    }
 }
@@ -8,8 +8,8 @@ use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, Shar
 use reth_interfaces::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_net_common::bandwidth_meter::BandwidthMeter;
 use reth_network_api::{
-    NetworkError, NetworkInfo, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
-    ReputationChangeKind,
+    AccessRule, NetworkError, NetworkInfo, PeerAccessList, PeerInfo, PeerKind, Peers, PeersInfo,
+    Reputation, ReputationChangeKind,
 };
 use reth_primitives::{Head, NodeRecord, PeerId, TransactionSigned, H256};
 use reth_rpc_types::NetworkStatus;
@@ -239,6 +239,22 @@ impl Peers for NetworkHandle {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn add_access_rule(&self, rule: AccessRule) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.add_access_rule(rule).await)
+    }
+
+    async fn remove_access_rule(&self, rule: AccessRule) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.remove_access_rule(rule).await)
+    }
+
+    async fn set_access_list(&self, access_list: PeerAccessList) -> Result<bool, NetworkError> {
+        Ok(self.inner.peers.set_access_list(access_list).await)
+    }
+
+    async fn access_list(&self) -> Result<PeerAccessList, NetworkError> {
+        Ok(self.inner.peers.access_list().await)
+    }
 }
 
 #[async_trait]
@@ -9,7 +9,10 @@ use crate::{
 };
 use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
-use reth_net_common::ban_list::BanList;
+use reth_net_common::{
+    access_list::{AccessRule, PeerAccessList},
+    ban_list::BanList,
+};
 use reth_network_api::{PeerKind, ReputationChangeKind};
 use reth_primitives::{ForkId, NodeRecord, PeerId};
 use std::{
@@ -17,7 +20,7 @@ use std::{
     fmt::Display,
     io::{self, ErrorKind},
     net::{IpAddr, SocketAddr},
-    path::Path,
+    path::{Path, PathBuf},
     task::{Context, Poll},
     time::Duration,
 };
@@ -27,7 +30,7 @@ use tokio::{
     time::{Instant, Interval},
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 
 /// A communication channel to the [`PeersManager`] to apply manual changes to the peer set.
 #[derive(Clone, Debug)]
@@ -73,6 +76,44 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Adds a rule to the access list.
+    ///
+    /// Returns false if the rule was already present.
+    pub async fn add_access_rule(&self, rule: AccessRule) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::AddAccessRule(rule, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Removes a rule from the access list.
+    ///
+    /// Returns false if the rule was not present.
+    pub async fn remove_access_rule(&self, rule: AccessRule) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::RemoveAccessRule(rule, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Replaces the access list.
+    ///
+    /// Returns false if the list is unchanged.
+    pub async fn set_access_list(&self, access_list: PeerAccessList) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::SetAccessList(access_list, tx));
+
+        rx.await.unwrap_or_default()
+    }
+
+    /// Returns the current access list.
+    pub async fn access_list(&self) -> PeerAccessList {
+        let (tx, rx) = oneshot::channel();
+        self.send(PeerCommand::GetAccessList(tx));
+
+        rx.await.unwrap_or_default()
+    }
 }
 
 /// Maintains the state of _all_ the peers known to the network.
@@ -99,6 +140,10 @@ pub struct PeersManager {
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
+    /// Operator managed allow and deny lists.
+    access_list: PeerAccessList,
+    /// Persists the access list when it's modified, if a file is configured.
+    access_list_writer: Option<AccessListWriter>,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
     /// Interval at which to check for peers to unban and release from the backoff map.
@@ -124,6 +169,8 @@ impl PeersManager {
             connection_info,
             reputation_weights,
             ban_list,
+            access_list,
+            access_list_file,
             ban_duration,
             backoff_durations,
             trusted_nodes,
@@ -160,6 +207,8 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info,
             ban_list,
+            access_list,
+            access_list_writer: access_list_file.and_then(AccessListWriter::spawn),
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
@@ -203,6 +252,77 @@ impl PeersManager {
         self.backed_off_peers.len()
     }
 
+    /// Adds the rule to the access list and applies it to all known peers.
+    ///
+    /// Returns false if the rule was already present.
+    pub(crate) fn add_access_rule(&mut self, rule: AccessRule) -> bool {
+        let added = self.access_list.add_rule(rule);
+        if added {
+            self.on_access_list_changed();
+        }
+        added
+    }
+
+    /// Removes the rule from the access list.
+    ///
+    /// Returns false if the rule was not present.
+    pub(crate) fn remove_access_rule(&mut self, rule: &AccessRule) -> bool {
+        let removed = self.access_list.remove_rule(rule);
+        if removed {
+            self.on_access_list_changed();
+        }
+        removed
+    }
+
+    /// Replaces the access list and applies it to all known peers.
+    ///
+    /// Returns false if the list is unchanged.
+    pub(crate) fn set_access_list(&mut self, access_list: PeerAccessList) -> bool {
+        if self.access_list == access_list {
+            return false
+        }
+        self.access_list = access_list;
+        self.on_access_list_changed();
+        true
+    }
+
+    /// Removes all peers that are no longer permitted by the access list, disconnecting active
+    /// sessions, and persists the list.
+    fn on_access_list_changed(&mut self) {
+        let denied = self
+            .peers
+            .iter()
+            .filter(|(peer_id, peer)| !self.access_list.is_permitted(peer_id, &peer.addr.ip()))
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+
+        for peer_id in denied {
+            // unlike `remove_peer` this also applies to trusted peers
+            let Some(mut peer) = self.peers.remove(&peer_id) else { continue };
+
+            trace!(target : "net::peers", ?peer_id, "remove denied node");
+            self.queued_actions.push_back(PeerAction::PeerRemoved(peer_id));
+
+            if peer.state.is_connected() {
+                debug!(target : "net::peers", ?peer_id, "disconnecting denied node");
+                // a trusted peer would be kept once the session is closed, see
+                // `on_active_session_gracefully_closed`
+                peer.kind = PeerKind::Basic;
+                peer.remove_after_disconnect = true;
+                peer.state.disconnect();
+                self.peers.insert(peer_id, peer);
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                })
+            }
+        }
+
+        if let Some(writer) = self.access_list_writer.as_ref() {
+            writer.save(self.access_list.clone());
+        }
+    }
+
     /// Invoked when a new _incoming_ tcp connection is accepted.
     ///
     /// returns an error if the inbound ip address is on the ban list or
@@ -214,6 +334,9 @@ impl PeersManager {
         if self.ban_list.is_banned_ip(&addr) {
             return Err(InboundConnectionError::IpBanned)
         }
+        if !self.access_list.is_ip_permitted(&addr) {
+            return Err(InboundConnectionError::IpDenied)
+        }
         if !self.connection_info.has_in_capacity() {
             return Err(InboundConnectionError::ExceedsLimit(self.connection_info.max_inbound))
         }
@@ -260,7 +383,9 @@ impl PeersManager {
     pub(crate) fn on_incoming_session_established(&mut self, peer_id: PeerId, addr: SocketAddr) {
         // we only need to check the peer id here as the ip address will have been checked at
         // on_inbound_pending_session. We also check if the peer is in the backoff list here.
-        if self.ban_list.is_banned_peer(&peer_id) {
+        if self.ban_list.is_banned_peer(&peer_id) ||
+            !self.access_list.is_permitted(&peer_id, &addr.ip())
+        {
             self.queued_actions.push_back(PeerAction::DisconnectBannedIncoming { peer_id });
             return
         }
@@ -570,7 +695,9 @@ impl PeersManager {
         addr: SocketAddr,
         fork_id: Option<ForkId>,
     ) {
-        if self.ban_list.is_banned(&peer_id, &addr.ip()) {
+        if self.ban_list.is_banned(&peer_id, &addr.ip()) ||
+            !self.access_list.is_permitted(&peer_id, &addr.ip())
+        {
             return
         }
 
@@ -647,10 +774,11 @@ impl PeersManager {
     ///
     /// Returns `None` if no peer is available.
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
-        let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
+        let mut unconnected = self.peers.iter_mut().filter(|(peer_id, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
                 peer.state.is_unconnected() &&
+                self.access_list.is_permitted(peer_id, &peer.addr.ip()) &&
                 (!self.connect_trusted_nodes_only || peer.is_trusted())
         });
 
@@ -740,6 +868,18 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::AddAccessRule(rule, tx) => {
+                        let _ = tx.send(self.add_access_rule(rule));
+                    }
+                    PeerCommand::RemoveAccessRule(rule, tx) => {
+                        let _ = tx.send(self.remove_access_rule(&rule));
+                    }
+                    PeerCommand::SetAccessList(access_list, tx) => {
+                        let _ = tx.send(self.set_access_list(access_list));
+                    }
+                    PeerCommand::GetAccessList(tx) => {
+                        let _ = tx.send(self.access_list.clone());
+                    }
                 }
             }
 
@@ -1020,6 +1160,50 @@ impl PeerConnectionState {
     }
 }
 
+/// Persists snapshots of the access list on a dedicated thread, so that writing the file never
+/// blocks the [`PeersManager`].
+///
+/// Snapshots are written in order. If the list changes faster than it can be written, only the
+/// latest snapshot is written.
+#[derive(Debug)]
+struct AccessListWriter {
+    tx: std::sync::mpsc::Sender<PeerAccessList>,
+}
+
+impl AccessListWriter {
+    /// Spawns the thread that writes to the given file.
+    ///
+    /// Returns `None` if the thread could not be spawned.
+    fn spawn(file: PathBuf) -> Option<Self> {
+        let (tx, rx) = std::sync::mpsc::channel::<PeerAccessList>();
+        let res = std::thread::Builder::new().name("access-list-writer".to_string()).spawn(
+            move || {
+                // exits once the manager and thus the sender is dropped
+                while let Ok(mut access_list) = rx.recv() {
+                    while let Ok(latest) = rx.try_recv() {
+                        access_list = latest;
+                    }
+                    if let Err(err) = access_list.save(&file) {
+                        warn!(target : "net::peers", ?err, file = %file.display(), "Failed to persist access list");
+                    }
+                }
+            },
+        );
+        match res {
+            Ok(_) => Some(Self { tx }),
+            Err(err) => {
+                warn!(target : "net::peers", ?err, "Failed to spawn access list writer, changes won't be persisted");
+                None
+            }
+        }
+    }
+
+    /// Queues the snapshot to be written.
+    fn save(&self, access_list: PeerAccessList) {
+        let _ = self.tx.send(access_list);
+    }
+}
+
 /// Commands the [`PeersManager`] listens for.
 #[derive(Debug)]
 pub(crate) enum PeerCommand {
@@ -1035,6 +1219,14 @@ pub(crate) enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Add a rule to the access list, replies whether the list changed.
+    AddAccessRule(AccessRule, oneshot::Sender<bool>),
+    /// Remove a rule from the access list, replies whether the list changed.
+    RemoveAccessRule(AccessRule, oneshot::Sender<bool>),
+    /// Replace the access list, replies whether the list changed.
+    SetAccessList(PeerAccessList, oneshot::Sender<bool>),
+    /// Get the current access list.
+    GetAccessList(oneshot::Sender<PeerAccessList>),
 }
 
 /// Actions the peer manager can trigger.
@@ -1119,6 +1311,12 @@ pub struct PeersConfig {
     /// Restrictions on PeerIds and Ips.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ban_list: BanList,
+    /// Operator managed allow and deny lists for PeerIds and Ip ranges.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub access_list: PeerAccessList,
+    /// File the access list is persisted to when it's modified at runtime.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub access_list_file: Option<PathBuf>,
     /// Restrictions on connections.
     pub connection_info: ConnectionInfo,
     /// How to weigh reputation changes.
@@ -1137,6 +1335,8 @@ impl Default for PeersConfig {
             connection_info: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
+            access_list: Default::default(),
+            access_list_file: None,
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
             backoff_durations: Default::default(),
@@ -1155,6 +1355,28 @@ impl PeersConfig {
         self
     }
 
+    /// Allow and deny rules for peer_ids and ip ranges that are enforced on connect.
+    pub fn with_access_list(mut self, access_list: PeerAccessList) -> Self {
+        self.access_list = access_list;
+        self
+    }
+
+    /// Read the access list from the given file, which is also where changes made at runtime are
+    /// persisted to.
+    ///
+    /// A missing file is treated as an empty access list.
+    pub fn with_access_list_file(
+        mut self,
+        file_path: impl Into<PathBuf>,
+    ) -> Result<Self, io::Error> {
+        let file_path = file_path.into();
+        let access_list = PeerAccessList::load(&file_path)?;
+        info!(target: "net::peers", file = %file_path.display(), rules = access_list.rules().len(), "Loaded access list");
+        self.access_list = access_list;
+        self.access_list_file = Some(file_path);
+        Ok(self)
+    }
+
     /// Maximum occupied slots for outbound connections.
     pub fn with_max_pending_outbound(mut self, num_outbound: usize) -> Self {
         self.connection_info.num_outbound = num_outbound;
@@ -1297,6 +1519,7 @@ impl Default for PeerBackoffDurations {
 pub enum InboundConnectionError {
     ExceedsLimit(usize),
     IpBanned,
+    IpDenied,
 }
 
 impl Display for InboundConnectionError {
@@ -1323,7 +1546,10 @@ mod test {
         errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
         DisconnectReason,
     };
-    use reth_net_common::ban_list::BanList;
+    use reth_net_common::{
+        access_list::{AccessRule, AccessTarget, PeerAccessList},
+        ban_list::BanList,
+    };
     use reth_network_api::ReputationChangeKind;
     use reth_primitives::{PeerId, H512};
     use std::{
@@ -1996,7 +2222,8 @@ mod test {
             Ok(_) => panic!(),
             Err(err) => match err {
                 super::InboundConnectionError::IpBanned {} => {}
-                super::InboundConnectionError::ExceedsLimit { .. } => {
+                super::InboundConnectionError::ExceedsLimit { .. } |
+                super::InboundConnectionError::IpDenied => {
                     panic!()
                 }
             },
@@ -2022,6 +2249,163 @@ mod test {
         assert_eq!(peer_id, given_peer_id)
     }
 
+    #[tokio::test]
+    async fn test_on_pending_access_list() {
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let access_list = PeerAccessList::new([AccessRule::deny(AccessTarget::Range(
+            "127.0.0.0/16".parse().unwrap(),
+        ))]);
+        let config = PeersConfig::default().with_access_list(access_list);
+        let mut peer_manager = PeersManager::new(config);
+
+        assert!(matches!(
+            peer_manager.on_incoming_pending_session(ip),
+            Err(super::InboundConnectionError::IpDenied)
+        ));
+        assert!(peer_manager
+            .on_incoming_pending_session(IpAddr::V4(Ipv4Addr::new(127, 1, 0, 1)))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_on_active_inbound_access_list() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let allowed_peer_id = PeerId::random();
+        let access_list =
+            PeerAccessList::new([AccessRule::allow(AccessTarget::Peer(allowed_peer_id))]);
+        let config = PeersConfig::default().with_access_list(access_list);
+        let mut peer_manager = PeersManager::new(config);
+
+        // the peer id is not known yet
+        assert!(peer_manager.on_incoming_pending_session(socket_addr.ip()).is_ok());
+
+        let given_peer_id = PeerId::random();
+        peer_manager.on_incoming_session_established(given_peer_id, socket_addr);
+        let Some(PeerAction::DisconnectBannedIncoming { peer_id }) =
+            peer_manager.queued_actions.pop_front()
+        else {
+            panic!()
+        };
+        assert_eq!(peer_id, given_peer_id);
+
+        peer_manager.on_incoming_session_established(allowed_peer_id, socket_addr);
+        assert!(matches!(
+            peer_manager.queued_actions.pop_front(),
+            Some(PeerAction::PeerAdded(peer_id)) if peer_id == allowed_peer_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_access_rule_disconnects_denied() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, socket_addr, None);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        let rule = AccessRule::deny(AccessTarget::Range("127.0.0.0/8".parse().unwrap()));
+        assert!(peers.add_access_rule(rule));
+
+        match event!(peers) {
+            PeerAction::PeerRemoved(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        // denied peers are not dialed nor added again
+        let other = PeerId::random();
+        peers.add_peer(other, socket_addr, None);
+        assert!(!peers.peers.contains_key(&other));
+
+        assert!(!peers.add_access_rule(rule));
+        assert!(peers.remove_access_rule(&rule));
+        assert!(!peers.remove_access_rule(&rule));
+        peers.add_peer(other, socket_addr, None);
+        assert!(peers.peers.contains_key(&other));
+    }
+
+    #[tokio::test]
+    async fn test_access_list_removes_trusted_after_disconnect() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_trusted_peer(peer, socket_addr);
+
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Connect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        let access_list = PeerAccessList::new([AccessRule::deny(AccessTarget::Peer(peer))]);
+        assert!(peers.set_access_list(access_list.clone()));
+        assert!(!peers.set_access_list(access_list));
+
+        match event!(peers) {
+            PeerAction::PeerRemoved(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        // the trusted peer is removed once the session is closed
+        peers.on_active_session_gracefully_closed(peer);
+        assert!(!peers.peers.contains_key(&peer));
+    }
+
+    #[tokio::test]
+    async fn test_persist_access_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("access-list");
+        let config = PeersConfig::default().with_access_list_file(&file).unwrap();
+        let mut peers = PeersManager::new(config);
+
+        let rule = AccessRule::deny(AccessTarget::Range("127.0.0.0/8".parse().unwrap()));
+        assert!(peers.add_access_rule(rule));
+
+        // the list is written in the background
+        let expected = PeerAccessList::new([rule]);
+        for _ in 0..100 {
+            if PeerAccessList::load(&file).unwrap() == expected {
+                return
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("access list was not persisted");
+    }
+
     #[test]
     fn test_connection_limits() {
         let mut info = ConnectionInfo::default();
@@ -209,6 +209,9 @@ where
                         InboundConnectionError::IpBanned => {
                             trace!(target: "net", ?remote_addr, "The incoming ip address is in the ban list");
                         }
+                        InboundConnectionError::IpDenied => {
+                            trace!(target: "net", ?remote_addr, "The incoming ip address is denied by the access list");
+                        }
                         InboundConnectionError::ExceedsLimit(limit) => {
                             trace!(target: "net", %limit, ?remote_addr, "Exceeded incoming connection limit; disconnecting");
                             self.sessions.disconnect_incoming_connection(
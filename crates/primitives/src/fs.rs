@@ -2,6 +2,7 @@
 use std::{
     fs, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 /// Various error variants for `std::fs` operations that serve as an addition to the io::Error which
@@ -24,6 +25,9 @@ pub enum FsPathError {
     /// Provides additional path context for [`std::fs::remove_file`].
     #[error("failed to remove file {path:?}: {source}")]
     RemoveFile { source: io::Error, path: PathBuf },
+    /// Provides additional path context for [`std::fs::rename`].
+    #[error("failed to rename {from:?} to {to:?}: {source}")]
+    Rename { source: io::Error, from: PathBuf, to: PathBuf },
     /// Provides additional path context for [`std::fs::create_dir`].
     #[error("failed to create dir {path:?}: {source}")]
     CreateDir { source: io::Error, path: PathBuf },
@@ -67,6 +71,11 @@ impl FsPathError {
         FsPathError::RemoveFile { source, path: path.into() }
     }
 
+    /// Returns the complementary error variant for [`std::fs::rename`].
+    pub fn rename(source: io::Error, from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
+        FsPathError::Rename { source, from: from.into(), to: to.into() }
+    }
+
     /// Returns the complementary error variant for [`std::fs::create_dir`].
     pub fn create_dir(source: io::Error, path: impl Into<PathBuf>) -> Self {
         FsPathError::CreateDir { source, path: path.into() }
@@ -98,9 +107,120 @@ pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
 }
 
 /// Wrapper for `std::fs::remove_dir_all`
+///
+/// Retries while a file in the directory is still in use, see [remove_file].
 pub fn remove_dir_all(path: impl AsRef<Path>) -> Result<()> {
     let path = path.as_ref();
-    fs::remove_dir_all(path).map_err(|err| FsPathError::remove_dir(err, path))
+    retry_in_use(|| fs::remove_dir_all(path)).map_err(|err| FsPathError::remove_dir(err, path))
+}
+
+/// How a file operation that may be deferred on Windows was completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    /// The operation was applied immediately.
+    Done,
+    /// The file was still in use, so the operation is applied by the OS on the next reboot.
+    OnReboot,
+}
+
+/// Wrapper for `std::fs::remove_file`
+///
+/// On Windows, a file can't be removed while another process holds a handle to it without
+/// `FILE_SHARE_DELETE`, e.g. an antivirus scanner or a backup tool, which fails with a sharing
+/// or lock violation. Such failures are retried with a backoff, and if the file is still in use
+/// afterwards its removal is scheduled for the next reboot.
+pub fn remove_file(path: impl AsRef<Path>) -> Result<Completion> {
+    let path = path.as_ref();
+    match retry_in_use(|| fs::remove_file(path)) {
+        Ok(()) => Ok(Completion::Done),
+        Err(err) if is_in_use(&err) => remove_on_reboot(path)
+            .map(|_| Completion::OnReboot)
+            .map_err(|_| FsPathError::remove_file(err, path)),
+        Err(err) => Err(FsPathError::remove_file(err, path)),
+    }
+}
+
+/// Wrapper for `std::fs::rename`, replacing `to` if it exists.
+///
+/// Like [remove_file], this retries while either file is in use. Unlike a removal, a rename is
+/// never deferred to the next reboot: callers rely on `to` having the new content once this
+/// returns, so an error is returned if either file is still in use afterwards.
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    retry_in_use(|| fs::rename(from, to)).map_err(|err| FsPathError::rename(err, from, to))
+}
+
+/// Maximum number of retries of an operation on a file that is in use.
+const IN_USE_RETRIES: u32 = 8;
+
+/// Delay before the first retry, doubled after every attempt.
+const IN_USE_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Runs the operation, retrying with an exponential backoff as long as it fails because the file
+/// is in use.
+fn retry_in_use<T>(op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    retry_if(op, is_in_use, IN_USE_RETRY_DELAY)
+}
+
+/// Runs the operation, retrying up to [IN_USE_RETRIES] times with an exponential backoff starting
+/// at `delay` as long as the error is transient.
+fn retry_if<T>(
+    mut op: impl FnMut() -> io::Result<T>,
+    is_transient: impl Fn(&io::Error) -> bool,
+    mut delay: Duration,
+) -> io::Result<T> {
+    for _ in 0..IN_USE_RETRIES {
+        match op() {
+            Err(err) if is_transient(&err) => {
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            res => return res,
+        }
+    }
+    op()
+}
+
+/// Returns true if the error is caused by another handle to the file, which is only the case on
+/// Windows.
+///
+/// Access denied is not considered transient: it is mostly caused by permissions or a read-only
+/// file, which retrying doesn't fix.
+#[cfg(windows)]
+fn is_in_use(err: &io::Error) -> bool {
+    use windows_sys::Win32::Foundation::{ERROR_LOCK_VIOLATION, ERROR_SHARING_VIOLATION};
+
+    matches!(
+        err.raw_os_error().map(|code| code as u32),
+        Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+    )
+}
+
+#[cfg(not(windows))]
+fn is_in_use(_err: &io::Error) -> bool {
+    false
+}
+
+/// Schedules removing the file on the next reboot.
+#[cfg(windows)]
+fn remove_on_reboot(path: &Path) -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{MoveFileExW, MOVEFILE_DELAY_UNTIL_REBOOT};
+
+    // the OS requires an absolute, nul terminated path
+    let path = path.canonicalize()?;
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+
+    // SAFETY: the path is nul terminated and outlives the call.
+    if unsafe { MoveFileExW(path.as_ptr(), std::ptr::null(), MOVEFILE_DELAY_UNTIL_REBOOT) } == 0 {
+        return Err(io::Error::last_os_error())
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn remove_on_reboot(_path: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
 }
 
 /// Wrapper for `std::fs::create_dir_all`
@@ -108,3 +228,74 @@ pub fn create_dir_all(path: impl AsRef<Path>) -> Result<()> {
     let path = path.as_ref();
     fs::create_dir_all(path).map_err(|err| FsPathError::create_dir(err, path))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_and_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let (from, to) = (dir.path().join("from"), dir.path().join("to"));
+        write(&from, b"new").unwrap();
+        write(&to, b"old").unwrap();
+
+        rename(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(read_to_string(&to).unwrap(), "new");
+
+        assert_eq!(remove_file(&to).unwrap(), Completion::Done);
+        assert!(!to.exists());
+        assert!(matches!(remove_file(&to), Err(FsPathError::RemoveFile { .. })));
+        assert!(matches!(rename(&from, &to), Err(FsPathError::Rename { .. })));
+    }
+
+    #[test]
+    fn retry_transient_errors() {
+        // the raw code of a sharing violation on Windows
+        let in_use = || io::Error::from_raw_os_error(32);
+        let is_transient = |err: &io::Error| err.raw_os_error() == Some(32);
+
+        // succeeds once the file is no longer in use
+        let mut attempts = 0;
+        let res = retry_if(
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err(in_use())
+                } else {
+                    Ok(attempts)
+                }
+            },
+            is_transient,
+            Duration::ZERO,
+        );
+        assert_eq!(res.unwrap(), 3);
+
+        // gives up with the last error
+        let mut attempts = 0;
+        let res: io::Result<()> = retry_if(
+            || {
+                attempts += 1;
+                Err(in_use())
+            },
+            is_transient,
+            Duration::ZERO,
+        );
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(32));
+        assert_eq!(attempts, IN_USE_RETRIES + 1);
+
+        // other errors are returned immediately
+        let mut attempts = 0;
+        let res: io::Result<()> = retry_if(
+            || {
+                attempts += 1;
+                Err(io::ErrorKind::PermissionDenied.into())
+            },
+            is_transient,
+            Duration::ZERO,
+        );
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(attempts, 1);
+    }
+}
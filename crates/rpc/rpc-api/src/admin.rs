@@ -46,4 +46,30 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Adds a rule to the peer access list, e.g. `deny 203.0.113.0/24` or `allow <node id>`.
+    ///
+    /// Known peers that are denied by the updated list are disconnected.
+    ///
+    /// Returns false if the rule was already present.
+    #[method(name = "addAccessRule")]
+    async fn add_access_rule(&self, rule: String) -> RpcResult<bool>;
+
+    /// Removes a rule from the peer access list.
+    ///
+    /// Returns false if the rule was not present.
+    #[method(name = "removeAccessRule")]
+    async fn remove_access_rule(&self, rule: String) -> RpcResult<bool>;
+
+    /// Returns all rules of the peer access list.
+    #[method(name = "accessList")]
+    async fn access_list(&self) -> RpcResult<Vec<String>>;
+
+    /// Replaces the peer access list with the given rules.
+    ///
+    /// Known peers that are denied by the new list are disconnected.
+    ///
+    /// Returns false if the list is unchanged.
+    #[method(name = "importAccessList")]
+    async fn import_access_list(&self, rules: Vec<String>) -> RpcResult<bool>;
 }
@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_rpc_types::{Filter, FilterChanges, FilterId, Log};
+use reth_rpc_types::{Filter, FilterChanges, FilterId, Log, LogsPage, LogsPageOptions};
 
 /// Rpc Interface for poll-based ethereum filter API.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -32,4 +32,29 @@ pub trait EthFilterApi {
     /// Returns logs matching given filter object.
     #[method(name = "getLogs")]
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+    /// Returns a single page of logs matching given filter object.
+    ///
+    /// The returned page contains at most `limit` logs and a `nextPageToken` that can be passed
+    /// back to fetch the next page, if the range is not exhausted yet.
+    #[method(name = "getLogsPage")]
+    async fn logs_page(
+        &self,
+        filter: Filter,
+        options: Option<LogsPageOptions>,
+    ) -> RpcResult<LogsPage>;
+
+    /// Streams all logs matching given filter object as a sequence of pages.
+    ///
+    /// Each notification is a [LogsPage], the last one has no `nextPageToken`.
+    #[subscription(
+        name = "subscribeLogsPages" => "logsPage",
+        unsubscribe = "unsubscribeLogsPages",
+        item = LogsPage
+    )]
+    async fn logs_pages(
+        &self,
+        filter: Filter,
+        limit: Option<u64>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 }
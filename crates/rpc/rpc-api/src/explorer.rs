@@ -0,0 +1,35 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::Address;
+use reth_rpc_types::{AddressActivity, ContractCreation, HistoryRange, TokenTransfer};
+
+/// Explorer rpc interface.
+///
+/// Serves per address aggregates for block explorers from an index that is maintained by the
+/// node. All responses only cover the indexed blocks, see `explorer_indexedRange`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "explorer"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "explorer"))]
+#[async_trait::async_trait]
+pub trait ExplorerApi {
+    /// Returns the range of indexed blocks, or `None` if no block was indexed yet.
+    #[method(name = "indexedRange")]
+    async fn indexed_range(&self) -> RpcResult<Option<HistoryRange>>;
+
+    /// Returns the transaction count and the first and last block the address was active in.
+    #[method(name = "getAddressActivity")]
+    async fn get_address_activity(&self, address: Address) -> RpcResult<Option<AddressActivity>>;
+
+    /// Returns the creator and creation transaction of the contract.
+    ///
+    /// Only contracts created by a transaction are indexed, not contracts created by another
+    /// contract.
+    #[method(name = "getContractCreation")]
+    async fn get_contract_creation(&self, address: Address) -> RpcResult<Option<ContractCreation>>;
+
+    /// Returns the most recent token transfers from or to the address, newest first.
+    #[method(name = "getTokenTransfers")]
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<TokenTransfer>>;
+}
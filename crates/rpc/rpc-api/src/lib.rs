@@ -21,6 +21,7 @@ mod engine;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
+mod explorer;
 mod net;
 mod otterscan;
 mod reth;
@@ -41,6 +42,7 @@ pub mod servers {
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
+        explorer::ExplorerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
         reth::RethApiServer,
@@ -64,6 +66,7 @@ pub mod clients {
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
         eth_filter::EthFilterApiClient,
+        explorer::ExplorerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
         rpc::RpcApiServer,
@@ -1,5 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, U256};
+use reth_rpc_types::HistoryRanges;
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +13,8 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the ranges of blocks for which historical data is available locally
+    #[method(name = "historyRanges")]
+    async fn reth_history_ranges(&self) -> RpcResult<HistoryRanges>;
 }
@@ -12,8 +12,8 @@ use jsonrpsee::{
 };
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
+    ReceiptProviderIdExt, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{cache::EthStateCache, gas_oracle::GasPriceOracle},
@@ -46,6 +46,7 @@ where
         + HeaderProvider
         + ReceiptProviderIdExt
         + StateProviderFactory
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -92,6 +93,7 @@ where
         + EvmEnvProvider
         + HeaderProvider
         + StateProviderFactory
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
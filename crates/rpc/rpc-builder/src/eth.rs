@@ -3,9 +3,9 @@ use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
-        RPC_DEFAULT_GAS_CAP,
+        CallLimits, RPC_DEFAULT_GAS_CAP, RPC_DEFAULT_MEMORY_LIMIT,
     },
-    EthApi, EthFilter, EthPubSub, TracingCallPool,
+    EthApi, EthFilter, EthPubSub, TraceCache, TraceCacheConfig, TracingCallPool,
 };
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +22,8 @@ pub struct EthHandlers<Provider, Pool, Network, Events> {
     pub pubsub: EthPubSub<Provider, Pool, Events, Network>,
     /// The configured tracing call pool
     pub tracing_call_pool: TracingCallPool,
+    /// The cache for the traces of recent blocks, if enabled
+    pub trace_cache: Option<TraceCache>,
 }
 
 /// Additional config values for the eth namespace
@@ -39,6 +41,20 @@ pub struct EthConfig {
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
     pub rpc_gas_cap: u64,
+    /// Maximum code size of state overrides in `eth_call` and call tracing RPC methods.
+    ///
+    /// Defaults to no limit.
+    pub rpc_max_code_size: Option<usize>,
+    /// EVM memory limit in bytes for `eth_call` and call tracing RPC methods.
+    ///
+    /// Defaults to [RPC_DEFAULT_MEMORY_LIMIT]
+    pub rpc_memory_limit: Option<u64>,
+    /// Overrides of the call limits for individual transports.
+    pub transport_call_limits: TransportCallLimits,
+    /// Settings for the disk-backed cache of block traces.
+    ///
+    /// Disabled if `None`.
+    pub trace_cache: Option<TraceCacheConfig>,
 }
 
 impl Default for EthConfig {
@@ -49,6 +65,10 @@ impl Default for EthConfig {
             max_tracing_requests: DEFAULT_MAX_TRACING_REQUESTS,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_max_code_size: None,
+            rpc_memory_limit: Some(RPC_DEFAULT_MEMORY_LIMIT),
+            transport_call_limits: TransportCallLimits::default(),
+            trace_cache: None,
         }
     }
 }
@@ -83,4 +103,81 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the maximum code size of state overrides
+    pub fn rpc_max_code_size(mut self, rpc_max_code_size: Option<usize>) -> Self {
+        self.rpc_max_code_size = rpc_max_code_size;
+        self
+    }
+
+    /// Configures the EVM memory limit for `eth_call` and call tracing RPC methods
+    pub fn rpc_memory_limit(mut self, rpc_memory_limit: Option<u64>) -> Self {
+        self.rpc_memory_limit = rpc_memory_limit;
+        self
+    }
+
+    /// Configures the per transport overrides of the call limits
+    pub fn transport_call_limits(mut self, transport_call_limits: TransportCallLimits) -> Self {
+        self.transport_call_limits = transport_call_limits;
+        self
+    }
+
+    /// Configures the disk-backed cache of block traces
+    pub fn trace_cache(mut self, trace_cache: Option<TraceCacheConfig>) -> Self {
+        self.trace_cache = trace_cache;
+        self
+    }
+
+    /// Returns the [CallLimits] that apply to all transports without an override.
+    pub fn call_limits(&self) -> CallLimits {
+        CallLimits::new(self.rpc_gas_cap)
+            .with_max_code_size(self.rpc_max_code_size)
+            .with_memory_limit(self.rpc_memory_limit)
+    }
+}
+
+/// Overrides of the [CallLimits] for individual transports.
+///
+/// Transports without an override use the limits configured in [EthConfig].
+///
+/// The limits are selected by the transport a request arrives on, not by the address of the
+/// caller. A server behind a reverse proxy sees all requests as coming from the proxy.
+///
+/// Note: if http and ws are served on the same port, the http limits apply to both.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransportCallLimits {
+    /// Limits for the http transport
+    pub http: Option<CallLimits>,
+    /// Limits for the ws transport
+    pub ws: Option<CallLimits>,
+    /// Limits for the ipc transport
+    pub ipc: Option<CallLimits>,
+    /// Limits for the `eth_` methods of the auth server
+    pub auth: Option<CallLimits>,
+}
+
+impl TransportCallLimits {
+    /// Configures the limits for the http transport
+    pub fn with_http(mut self, limits: CallLimits) -> Self {
+        self.http = Some(limits);
+        self
+    }
+
+    /// Configures the limits for the ws transport
+    pub fn with_ws(mut self, limits: CallLimits) -> Self {
+        self.ws = Some(limits);
+        self
+    }
+
+    /// Configures the limits for the ipc transport
+    pub fn with_ipc(mut self, limits: CallLimits) -> Self {
+        self.ipc = Some(limits);
+        self
+    }
+
+    /// Configures the limits for the `eth_` methods of the auth server
+    pub fn with_auth(mut self, limits: CallLimits) -> Self {
+        self.auth = Some(limits);
+        self
+    }
 }
@@ -18,13 +18,13 @@
 //!
 //! ```
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader, PruneCheckpointReader};
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
 //! pub async fn launch<Provider, Pool, Network, Events>(provider: Provider, pool: Pool, network: Network, events: Events)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + PruneCheckpointReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -51,7 +51,7 @@
 //! ```
 //! use tokio::try_join;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader};
+//! use reth_provider::{BlockReaderIdExt, ChainSpecProvider, CanonStateSubscriptions, StateProviderFactory, EvmEnvProvider, ChangeSetReader, PruneCheckpointReader};
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_builder::{RethRpcModule, RpcModuleBuilder, RpcServerConfig, TransportRpcModuleConfig};
 //! use reth_tasks::TokioTaskExecutor;
@@ -60,7 +60,7 @@
 //! use reth_rpc_builder::auth::AuthServerConfig;
 //! pub async fn launch<Provider, Pool, Network, Events, EngineApi>(provider: Provider, pool: Pool, network: Network, events: Events, engine_api: EngineApi)
 //! where
-//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
+//!     Provider: BlockReaderIdExt + ChainSpecProvider + ChangeSetReader + PruneCheckpointReader + StateProviderFactory + EvmEnvProvider + Clone + Unpin + 'static,
 //!     Pool: TransactionPool + Clone + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
 //!     Events: CanonStateSubscriptions +  Clone + 'static,
@@ -110,16 +110,18 @@ use reth_ipc::server::IpcServer;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
     BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, StateProviderFactory,
+    EvmEnvProvider, PruneCheckpointReader, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
         cache::{cache_new_blocks_task, EthStateCache},
         gas_oracle::GasPriceOracle,
+        CallLimits,
     },
+    explorer::explorer_index_task,
     AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TracingCallGuard, TracingCallPool, TxPoolApi,
-    Web3Api,
+    ExplorerApi, ExplorerConfig, ExplorerIndex, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
+    TraceCache, TracingCallGuard, TracingCallPool, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::{servers::*, EngineApiServer};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -134,7 +136,7 @@ use std::{
 use strum::{AsRefStr, EnumString, EnumVariantNames, ParseError, VariantNames};
 use tower::layer::util::{Identity, Stack};
 use tower_http::cors::CorsLayer;
-use tracing::{instrument, trace};
+use tracing::{instrument, trace, warn};
 
 /// Auth server utilities.
 pub mod auth;
@@ -155,7 +157,7 @@ pub mod constants;
 mod metrics;
 
 // re-export for convenience
-pub use crate::eth::{EthConfig, EthHandlers};
+pub use crate::eth::{EthConfig, EthHandlers, TransportCallLimits};
 pub use jsonrpsee::server::ServerBuilder;
 pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::noop::NoopNetwork;
@@ -177,6 +179,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -322,6 +325,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -362,10 +366,12 @@ where
             config.unwrap_or_default(),
         );
 
+        let call_limits = registry.transport_call_limits();
+
         modules.config = module_config;
-        modules.http = registry.maybe_module(http.as_ref());
-        modules.ws = registry.maybe_module(ws.as_ref());
-        modules.ipc = registry.maybe_module(ipc.as_ref());
+        modules.http = registry.maybe_module(http.as_ref(), call_limits.http);
+        modules.ws = registry.maybe_module(ws.as_ref(), call_limits.ws);
+        modules.ipc = registry.maybe_module(ipc.as_ref(), call_limits.ipc);
 
         let auth_module = registry.create_auth_module(engine);
 
@@ -393,10 +399,12 @@ where
                 config.unwrap_or_default(),
             );
 
+            let call_limits = registry.transport_call_limits();
+
             modules.config = module_config;
-            modules.http = registry.maybe_module(http.as_ref());
-            modules.ws = registry.maybe_module(ws.as_ref());
-            modules.ipc = registry.maybe_module(ipc.as_ref());
+            modules.http = registry.maybe_module(http.as_ref(), call_limits.http);
+            modules.ws = registry.maybe_module(ws.as_ref(), call_limits.ws);
+            modules.ipc = registry.maybe_module(ipc.as_ref(), call_limits.ipc);
         }
 
         modules
@@ -414,6 +422,9 @@ impl Default for RpcModuleBuilder<(), (), (), (), ()> {
 pub struct RpcModuleConfig {
     /// `eth` namespace settings
     eth: EthConfig,
+    /// `explorer` namespace settings
+    #[serde(default)]
+    explorer: ExplorerConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -425,7 +436,13 @@ impl RpcModuleConfig {
     }
     /// Returns a new RPC module config given the eth namespace config
     pub fn new(eth: EthConfig) -> Self {
-        Self { eth }
+        Self { eth, explorer: Default::default() }
+    }
+
+    /// Configures the explorer namespace
+    pub fn with_explorer(mut self, explorer: ExplorerConfig) -> Self {
+        self.explorer = explorer;
+        self
     }
 }
 
@@ -433,6 +450,7 @@ impl RpcModuleConfig {
 #[derive(Clone, Debug, Default)]
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
+    explorer: Option<ExplorerConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -444,10 +462,16 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures a custom explorer namespace config
+    pub fn explorer(mut self, explorer: ExplorerConfig) -> Self {
+        self.explorer = Some(explorer);
+        self
+    }
+
     /// Consumes the type and creates the [RpcModuleConfig]
     pub fn build(self) -> RpcModuleConfig {
-        let RpcModuleConfigBuilder { eth } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default() }
+        let RpcModuleConfigBuilder { eth, explorer } = self;
+        RpcModuleConfig { eth: eth.unwrap_or_default(), explorer: explorer.unwrap_or_default() }
     }
 }
 
@@ -479,11 +503,21 @@ impl RpcModuleSelection {
     pub const STANDARD_MODULES: [RethRpcModule; 3] =
         [RethRpcModule::Eth, RethRpcModule::Net, RethRpcModule::Web3];
 
-    /// Returns a selection of [RethRpcModule] with all [RethRpcModule::VARIANTS].
+    /// The modules that are only instantiated if they are selected explicitly.
+    ///
+    /// [RethRpcModule::Explorer] maintains an index of the chain in a background task, which is
+    /// only worth its cost if the namespace is actually used.
+    pub const OPT_IN_MODULES: [RethRpcModule; 1] = [RethRpcModule::Explorer];
+
+    /// Returns a selection of [RethRpcModule] with all [RethRpcModule::VARIANTS], except the
+    /// [RpcModuleSelection::OPT_IN_MODULES].
     pub fn all_modules() -> Vec<RethRpcModule> {
         RpcModuleSelection::try_from_selection(RethRpcModule::VARIANTS.iter().copied())
             .expect("valid selection")
             .into_selection()
+            .into_iter()
+            .filter(|module| !Self::OPT_IN_MODULES.contains(module))
+            .collect()
     }
 
     /// Returns the [RpcModuleSelection::STANDARD_MODULES] as a selection.
@@ -495,7 +529,8 @@ impl RpcModuleSelection {
 
     /// All modules that are available by default on IPC.
     ///
-    /// By default all modules are available on IPC.
+    /// By default all modules except the [RpcModuleSelection::OPT_IN_MODULES] are available on
+    /// IPC.
     pub fn default_ipc_modules() -> Vec<RethRpcModule> {
         Self::all_modules()
     }
@@ -570,6 +605,7 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -677,6 +713,8 @@ pub enum RethRpcModule {
     Reth,
     /// `ots_` module
     Ots,
+    /// `explorer_` module
+    Explorer,
 }
 
 // === impl RethRpcModule ===
@@ -715,6 +753,8 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events> {
     config: RpcModuleConfig,
     /// Holds a clone of all the eth namespace handlers
     eth: Option<EthHandlers<Provider, Pool, Network, Events>>,
+    /// The index served by the explorer namespace, created on first use
+    explorer: Option<ExplorerIndex>,
     /// to put trace calls behind semaphore
     tracing_call_guard: TracingCallGuard,
     /// Contains the [Methods] of a module
@@ -740,6 +780,7 @@ impl<Provider, Pool, Network, Tasks, Events>
             pool,
             network,
             eth: None,
+            explorer: None,
             executor,
             modules: Default::default(),
             tracing_call_guard: TracingCallGuard::new(config.eth.max_tracing_requests),
@@ -768,6 +809,11 @@ impl<Provider, Pool, Network, Tasks, Events>
         &self.provider
     }
 
+    /// Returns the configured per transport overrides of the call limits
+    pub fn transport_call_limits(&self) -> TransportCallLimits {
+        self.config.eth.transport_call_limits.clone()
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -811,6 +857,7 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -835,15 +882,16 @@ where
 
     /// Register Debug Namespace
     pub fn register_debug(&mut self) -> &mut Self {
-        let eth_api = self.eth_api();
+        let eth = self.eth_handlers();
         self.modules.insert(
             RethRpcModule::Debug,
             DebugApi::new(
                 self.provider.clone(),
-                eth_api,
+                eth.api,
                 Box::new(self.executor.clone()),
                 self.tracing_call_guard.clone(),
             )
+            .with_trace_cache(eth.trace_cache)
             .into_rpc()
             .into(),
         );
@@ -856,6 +904,7 @@ where
         self.modules.insert(
             RethRpcModule::Trace,
             TraceApi::new(self.provider.clone(), eth.api, self.tracing_call_guard.clone())
+                .with_trace_cache(eth.trace_cache)
                 .into_rpc()
                 .into(),
         );
@@ -877,7 +926,11 @@ where
         module.merge(engine_api.into_rpc()).expect("No conflicting methods");
 
         // also merge a subset of `eth_` handlers
-        let engine_eth = EngineEthApi::new(eth_handlers.api.clone(), eth_handlers.filter);
+        let eth_api = match self.config.eth.transport_call_limits.auth {
+            Some(limits) => eth_handlers.api.with_call_limits(limits),
+            None => eth_handlers.api,
+        };
+        let engine_eth = EngineEthApi::new(eth_api, eth_handlers.filter);
         module.merge(engine_eth.into_rpc()).expect("No conflicting methods");
 
         AuthRpcModule { inner: module }
@@ -902,10 +955,47 @@ where
         self
     }
 
+    /// Register Explorer namespace
+    pub fn register_explorer(&mut self) -> &mut Self {
+        let index = self.explorer_index();
+        self.modules.insert(RethRpcModule::Explorer, ExplorerApi::new(index).into_rpc().into());
+        self
+    }
+
+    /// Returns the [ExplorerIndex] served by the explorer namespace.
+    ///
+    /// This will spawn exactly one task that maintains the index if this is the first time the
+    /// index is requested.
+    pub fn explorer_index(&mut self) -> ExplorerIndex {
+        if let Some(index) = &self.explorer {
+            return index.clone()
+        }
+
+        let index = ExplorerIndex::new(self.config.explorer.clone());
+        let new_canonical_blocks = self.events.canonical_state_stream();
+        // the index only serves the explorer namespace, so it must not take down the node
+        self.executor.spawn_blocking(Box::pin(explorer_index_task(
+            index.clone(),
+            self.provider.clone(),
+            new_canonical_blocks,
+        )));
+        self.explorer = Some(index.clone());
+        index
+    }
+
     /// Helper function to create a [RpcModule] if it's not `None`
-    fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
+    ///
+    /// If `call_limits` are set, the module uses them instead of the default limits.
+    fn maybe_module(
+        &mut self,
+        config: Option<&RpcModuleSelection>,
+        call_limits: Option<CallLimits>,
+    ) -> Option<RpcModule<()>> {
         let config = config?;
-        let module = self.module_for(config);
+        let module = match call_limits {
+            Some(call_limits) => self.module_with_call_limits(config, call_limits),
+            None => self.module_for(config),
+        };
         Some(module)
     }
 
@@ -920,6 +1010,26 @@ where
         module
     }
 
+    /// Populates a new [RpcModule] based on the selected [RethRpcModule]s in the given
+    /// [RpcModuleSelection] that applies the given [CallLimits] to `eth_call` and adjacent calls.
+    ///
+    /// Unlike [Self::module_for], the created namespaces are not installed in this registry.
+    pub fn module_with_call_limits(
+        &mut self,
+        config: &RpcModuleSelection,
+        call_limits: CallLimits,
+    ) -> RpcModule<()> {
+        let mut eth = self.eth_handlers();
+        eth.api = eth.api.with_call_limits(call_limits);
+
+        let namespaces: Vec<_> = config.iter_selection().collect();
+        let mut module = RpcModule::new(());
+        for namespace in namespaces.iter().copied() {
+            module.merge(self.create_methods(namespace, &eth, &namespaces)).expect("No conflicts");
+        }
+        module
+    }
+
     /// Returns the [Methods] for the given [RethRpcModule]
     ///
     /// If this is the first time the namespace is requested, a new instance of API implementation
@@ -928,13 +1038,7 @@ where
         &mut self,
         namespaces: impl Iterator<Item = RethRpcModule>,
     ) -> Vec<Methods> {
-        let EthHandlers {
-            api: eth_api,
-            filter: eth_filter,
-            pubsub: eth_pubsub,
-            cache: _,
-            tracing_call_pool: _,
-        } = self.with_eth(|eth| eth.clone());
+        let eth = self.eth_handlers();
 
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
@@ -943,62 +1047,81 @@ where
             .iter()
             .copied()
             .map(|namespace| {
-                self.modules
-                    .entry(namespace)
-                    .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone()).into_rpc().into()
-                        }
-                        RethRpcModule::Debug => DebugApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            Box::new(self.executor.clone()),
-                            self.tracing_call_guard.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
-                        RethRpcModule::Eth => {
-                            // merge all eth handlers
-                            let mut module = eth_api.clone().into_rpc();
-                            module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
-                            module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
-
-                            module.into()
-                        }
-                        RethRpcModule::Net => {
-                            NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
-                        }
-                        RethRpcModule::Trace => TraceApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            self.tracing_call_guard.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
-                        RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
-                        }
-                        RethRpcModule::Rpc => RPCApi::new(
-                            namespaces
-                                .iter()
-                                .map(|module| (module.to_string(), "1.0".to_string()))
-                                .collect(),
-                        )
-                        .into_rpc()
-                        .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
-                    })
-                    .clone()
+                if let Some(methods) = self.modules.get(&namespace) {
+                    return methods.clone()
+                }
+                let methods = self.create_methods(namespace, &eth, &namespaces);
+                self.modules.insert(namespace, methods.clone());
+                methods
             })
             .collect::<Vec<_>>()
     }
 
+    /// Creates a new instance of the API implementation of the given [RethRpcModule].
+    ///
+    /// `namespaces` are all selected namespaces, which are listed by the `rpc_` namespace.
+    fn create_methods(
+        &mut self,
+        namespace: RethRpcModule,
+        eth: &EthHandlers<Provider, Pool, Network, Events>,
+        namespaces: &[RethRpcModule],
+    ) -> Methods {
+        let EthHandlers {
+            api: eth_api,
+            filter: eth_filter,
+            pubsub: eth_pubsub,
+            cache: _,
+            tracing_call_pool: _,
+            trace_cache,
+        } = eth;
+
+        match namespace {
+            RethRpcModule::Admin => AdminApi::new(self.network.clone()).into_rpc().into(),
+            RethRpcModule::Debug => DebugApi::new(
+                self.provider.clone(),
+                eth_api.clone(),
+                Box::new(self.executor.clone()),
+                self.tracing_call_guard.clone(),
+            )
+            .with_trace_cache(trace_cache.clone())
+            .into_rpc()
+            .into(),
+            RethRpcModule::Eth => {
+                // merge all eth handlers
+                let mut module = eth_api.clone().into_rpc();
+                module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
+                module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+
+                module.into()
+            }
+            RethRpcModule::Net => {
+                NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
+            }
+            RethRpcModule::Trace => TraceApi::new(
+                self.provider.clone(),
+                eth_api.clone(),
+                self.tracing_call_guard.clone(),
+            )
+            .with_trace_cache(trace_cache.clone())
+            .into_rpc()
+            .into(),
+            RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
+            RethRpcModule::Txpool => TxPoolApi::new(self.pool.clone()).into_rpc().into(),
+            RethRpcModule::Rpc => RPCApi::new(
+                namespaces.iter().map(|module| (module.to_string(), "1.0".to_string())).collect(),
+            )
+            .into_rpc()
+            .into(),
+            RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+            RethRpcModule::Reth => {
+                RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                    .into_rpc()
+                    .into()
+            }
+            RethRpcModule::Explorer => ExplorerApi::new(self.explorer_index()).into_rpc().into(),
+        }
+    }
+
     /// Returns the [EthStateCache] frontend
     ///
     /// This will spawn exactly one [EthStateCache] service if this is the first time the cache is
@@ -1043,7 +1166,8 @@ where
                 self.config.eth.rpc_gas_cap,
                 executor.clone(),
                 tracing_call_pool.clone(),
-            );
+            )
+            .with_call_limits(self.config.eth.call_limits());
             let filter = EthFilter::new(
                 self.provider.clone(),
                 self.pool.clone(),
@@ -1057,10 +1181,16 @@ where
                 self.pool.clone(),
                 self.events.clone(),
                 self.network.clone(),
-                executor,
+                executor.clone(),
             );
 
-            let eth = EthHandlers { api, cache, filter, pubsub, tracing_call_pool };
+            let trace_cache = self.config.eth.trace_cache.clone().and_then(|config| {
+                TraceCache::open(config, executor)
+                    .map_err(|err| warn!(target: "rpc", %err, "Failed to open trace cache"))
+                    .ok()
+            });
+
+            let eth = EthHandlers { api, cache, filter, pubsub, tracing_call_pool, trace_cache };
             self.eth = Some(eth);
         }
         f(self.eth.as_ref().expect("exists; qed"))
@@ -1844,6 +1974,8 @@ impl fmt::Debug for RpcServerHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
+    use reth_transaction_pool::test_utils::testing_pool;
 
     #[test]
     fn parse_rpc_module_selection() {
@@ -1902,9 +2034,37 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "explorer" => RethRpcModule::Explorer,
             );
     }
 
+    #[test]
+    fn test_all_modules_exclude_opt_in() {
+        let all = RpcModuleSelection::All.into_selection();
+        assert!(!all.contains(&RethRpcModule::Explorer));
+        assert!(all.contains(&RethRpcModule::Eth));
+        assert_eq!(RpcModuleSelection::default_ipc_modules(), all);
+    }
+
+    #[tokio::test]
+    async fn test_default_selection_does_not_spawn_explorer_index() {
+        let mut registry = RethModuleRegistry::new(
+            NoopProvider::default(),
+            testing_pool(),
+            NoopNetwork::default(),
+            TokioTaskExecutor::default(),
+            TestCanonStateSubscriptions::default(),
+            RpcModuleConfig::default(),
+        );
+        registry.module_for(&RpcModuleSelection::All);
+        registry
+            .module_for(&RpcModuleSelection::Selection(RpcModuleSelection::default_ipc_modules()));
+        assert!(registry.explorer.is_none());
+
+        registry.module_for(&RpcModuleSelection::Selection(vec![RethRpcModule::Explorer]));
+        assert!(registry.explorer.is_some());
+    }
+
     #[test]
     fn test_default_selection() {
         let selection = RpcModuleSelection::Standard.into_selection();
@@ -14,8 +14,8 @@ use reth_primitives::{
 };
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
-    Web3ApiClient,
+    DebugApiClient, EthFilterApiClient, ExplorerApiClient, NetApiClient, OtterscanClient,
+    TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_builder::RethRpcModule;
 use reth_rpc_types::{trace::filter::TraceFilter, CallRequest, Filter, Index, TransactionRequest};
@@ -40,6 +40,7 @@ where
     let id = EthFilterApiClient::new_block_filter(client).await.unwrap();
     EthFilterApiClient::filter_changes(client, id.clone()).await.unwrap();
     EthFilterApiClient::logs(client, Filter::default()).await.unwrap();
+    EthFilterApiClient::logs_page(client, Filter::default(), None).await.unwrap();
     let id = EthFilterApiClient::new_filter(client, Filter::default()).await.unwrap();
     EthFilterApiClient::filter_logs(client, id.clone()).await.unwrap();
     EthFilterApiClient::uninstall_filter(client, id).await.unwrap();
@@ -57,6 +58,11 @@ where
     AdminApiClient::add_trusted_peer(client, node).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::add_access_rule(client, "deny 203.0.113.0/24".to_string()).await.unwrap();
+    AdminApiClient::remove_access_rule(client, "deny 203.0.113.0/24".to_string()).await.unwrap();
+    AdminApiClient::access_list(client).await.unwrap();
+    AdminApiClient::import_access_list(client, vec!["allow 10.0.0.0/8".to_string()]).await.unwrap();
+    AdminApiClient::add_access_rule(client, "block 203.0.113.0/24".to_string()).await.unwrap_err();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -195,6 +201,21 @@ where
     Web3ApiClient::sha3(client, Bytes::default()).await.unwrap();
 }
 
+async fn test_basic_explorer_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let address = Address::default();
+
+    assert_eq!(ExplorerApiClient::indexed_range(client).await.unwrap(), None);
+    assert_eq!(ExplorerApiClient::get_address_activity(client, address).await.unwrap(), None);
+    assert_eq!(ExplorerApiClient::get_contract_creation(client, address).await.unwrap(), None);
+    assert!(ExplorerApiClient::get_token_transfers(client, address, None)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
 async fn test_basic_otterscan_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -452,3 +473,21 @@ async fn test_call_otterscan_functions_http_and_ws() {
     let client = handle.http_client().unwrap();
     test_basic_otterscan_calls(&client).await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_explorer_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Explorer]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_explorer_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_explorer_functions_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(vec![RethRpcModule::Explorer]).await;
+    let client = handle.ws_client().await.unwrap();
+    test_basic_explorer_calls(&client).await;
+}
@@ -16,6 +16,8 @@ pub enum EthRpcErrorCode {
     /// Thrown when querying for `finalized` or `safe` block before the merge transition is
     /// finalized, <https://github.com/ethereum/execution-apis/blob/6d17705a875e52c26826124c2a8a15ed542aeca2/src/schemas/block.yaml#L109>
     UnknownBlock,
+    /// Thrown when the requested data is no longer available locally, <https://eips.ethereum.org/EIPS/eip-1474>
+    ResourceUnavailable,
 }
 
 impl EthRpcErrorCode {
@@ -27,6 +29,7 @@ impl EthRpcErrorCode {
             EthRpcErrorCode::InvalidInput => -32000,
             EthRpcErrorCode::ResourceNotFound => -32001,
             EthRpcErrorCode::UnknownBlock => -39001,
+            EthRpcErrorCode::ResourceUnavailable => -32002,
         }
     }
 }
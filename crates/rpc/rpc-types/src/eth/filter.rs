@@ -873,6 +873,105 @@ impl<'de> Deserialize<'de> for FilterChanges {
     }
 }
 
+/// Cursor that identifies where the next page of a paginated log query starts.
+///
+/// The token also pins the resolved upper bound of the queried range, so paging through a range
+/// that ends at `latest` returns a consistent result even if new blocks are imported meanwhile.
+///
+/// Serialized as an opaque hex string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LogsPageToken {
+    /// Number of the block that contains the first log of the next page.
+    pub block_number: u64,
+    /// Index of the first log of the next page within its block.
+    pub log_index: u64,
+    /// Inclusive upper bound of the paginated block range.
+    pub to_block: u64,
+}
+
+impl LogsPageToken {
+    /// Length of the hex encoded token, without the `0x` prefix.
+    const ENCODED_LEN: usize = 48;
+}
+
+impl std::fmt::Display for LogsPageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:016x}{:016x}{:016x}", self.block_number, self.log_index, self.to_block)
+    }
+}
+
+impl std::str::FromStr for LogsPageToken {
+    type Err = LogsPageTokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        if s.len() != Self::ENCODED_LEN || !s.is_ascii() {
+            return Err(LogsPageTokenError)
+        }
+        let parse = |part: &str| u64::from_str_radix(part, 16).map_err(|_| LogsPageTokenError);
+        Ok(Self {
+            block_number: parse(&s[..16])?,
+            log_index: parse(&s[16..32])?,
+            to_block: parse(&s[32..])?,
+        })
+    }
+}
+
+impl Serialize for LogsPageToken {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LogsPageToken {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error returned when a [LogsPageToken] can't be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid logs page token")]
+pub struct LogsPageTokenError;
+
+/// Pagination options for `eth_getLogsPage`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPageOptions {
+    /// Continuation cursor returned by the previous page, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<LogsPageToken>,
+    /// Maximum number of logs to return in this page.
+    ///
+    /// This is capped by the node's configured max logs per response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+/// Response of the `eth_getLogsPage` RPC.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    /// Matching logs of this page.
+    pub logs: Vec<RpcLog>,
+    /// Cursor for the next page, `None` if this is the last page.
+    pub next_page_token: Option<LogsPageToken>,
+    /// Set on the last notification of `eth_subscribeLogsPages` if the stream was aborted
+    /// because a page could not be fetched.
+    ///
+    /// The `next_page_token` of that notification is the position at which the query failed, it
+    /// can be passed to `eth_getLogsPage` to resume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Owned equivalent of a `SubscriptionId`
 #[derive(Debug, PartialEq, Clone, Hash, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -1280,4 +1379,33 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn logs_page_token_roundtrip() {
+        let token = LogsPageToken { block_number: 17_000_000, log_index: 42, to_block: u64::MAX };
+        let s = token.to_string();
+        assert_eq!(s.len(), 2 + LogsPageToken::ENCODED_LEN);
+        assert_eq!(s.parse::<LogsPageToken>().unwrap(), token);
+
+        let json = serde_json::to_value(token).unwrap();
+        assert_eq!(serde_json::from_value::<LogsPageToken>(json).unwrap(), token);
+
+        assert!("0x1234".parse::<LogsPageToken>().is_err());
+        assert!(s.replace('0', "z").parse::<LogsPageToken>().is_err());
+    }
+
+    #[test]
+    fn can_deserialize_logs_page_options() {
+        let s = r#"{"pageToken":"0x0000000001036640000000000000002a0000000001036650","limit":100}"#;
+        let options: LogsPageOptions = serde_json::from_str(s).unwrap();
+        assert_eq!(options.limit, Some(100));
+        assert_eq!(
+            options.page_token,
+            Some(LogsPageToken { block_number: 17_000_000, log_index: 42, to_block: 17_000_016 })
+        );
+        assert_eq!(serde_json::to_string(&options).unwrap(), s);
+
+        let options: LogsPageOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options, LogsPageOptions::default());
+    }
 }
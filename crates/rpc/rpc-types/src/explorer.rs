@@ -0,0 +1,49 @@
+//! Types for the `explorer` namespace.
+
+use reth_primitives::{Address, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Custom struct for the `explorer_getAddressActivity` RPC response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressActivity {
+    /// Number of indexed transactions sent or received by the address, including the transaction
+    /// that created it if it's a contract
+    pub transaction_count: U64,
+    /// First indexed block the address was active in
+    pub first_seen: U64,
+    /// Last indexed block the address was active in
+    pub last_seen: U64,
+}
+
+/// Custom struct for the `explorer_getContractCreation` RPC response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCreation {
+    /// Sender of the transaction that created the contract
+    pub creator: Address,
+    /// Hash of the transaction that created the contract
+    pub transaction_hash: H256,
+    /// Number of the block that includes the transaction
+    pub block_number: U64,
+}
+
+/// An ERC-20 or ERC-721 `Transfer` event, as returned by `explorer_getTokenTransfers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    /// Contract that emitted the event
+    pub token: Address,
+    /// Sender of the tokens
+    pub from: Address,
+    /// Receiver of the tokens
+    pub to: Address,
+    /// Amount of ERC-20 tokens or id of the ERC-721 token
+    pub value: U256,
+    /// Hash of the transaction that emitted the event
+    pub transaction_hash: H256,
+    /// Number of the block that includes the transaction
+    pub block_number: U64,
+    /// Index of the event in the block
+    pub log_index: U64,
+}
@@ -13,10 +13,14 @@
 
 mod admin;
 mod eth;
+mod explorer;
 mod otterscan;
+mod reth;
 mod rpc;
 
 pub use admin::*;
 pub use eth::*;
+pub use explorer::*;
 pub use otterscan::*;
+pub use reth::*;
 pub use rpc::*;
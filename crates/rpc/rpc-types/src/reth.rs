@@ -0,0 +1,73 @@
+//! Types for the `reth` namespace.
+
+use reth_primitives::U64;
+use serde::{Deserialize, Serialize};
+
+/// An inclusive range of blocks for which some kind of historical data is available locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryRange {
+    /// The lowest available block
+    pub from: U64,
+    /// The highest available block
+    pub to: U64,
+}
+
+impl HistoryRange {
+    /// Creates a new range, returns `None` if `from` is higher than `to`, i.e. the range is empty.
+    pub fn new(from: u64, to: u64) -> Option<Self> {
+        (from <= to).then(|| Self { from: U64::from(from), to: U64::from(to) })
+    }
+
+    /// Returns true if the given block is included in the range.
+    pub fn contains(&self, block: u64) -> bool {
+        self.from.as_u64() <= block && block <= self.to.as_u64()
+    }
+}
+
+/// Custom struct for the `reth_historyRanges` RPC response.
+///
+/// Each field is the range of blocks that can be requested for the given kind of data, or `None`
+/// if no block is available.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRanges {
+    /// Headers and block bodies, including transactions
+    pub blocks: Option<HistoryRange>,
+    /// Transaction senders
+    pub senders: Option<HistoryRange>,
+    /// Transaction hash to number lookups, used by `eth_getTransactionByHash`
+    pub transaction_lookup: Option<HistoryRange>,
+    /// Transaction receipts and logs
+    ///
+    /// Note: receipts inside this range may still be pruned if contract log pruning is enabled.
+    pub receipts: Option<HistoryRange>,
+    /// Account state at a historical block
+    pub account_history: Option<HistoryRange>,
+    /// Storage state at a historical block
+    pub storage_history: Option<HistoryRange>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_history_ranges() {
+        let ranges = HistoryRanges {
+            blocks: HistoryRange::new(0, 100),
+            receipts: HistoryRange::new(90, 100),
+            senders: HistoryRange::new(101, 100),
+            ..Default::default()
+        };
+        assert!(ranges.senders.is_none());
+        assert!(ranges.receipts.unwrap().contains(90));
+        assert!(!ranges.receipts.unwrap().contains(89));
+
+        let s = serde_json::to_string(&ranges).unwrap();
+        assert_eq!(
+            s,
+            r#"{"blocks":{"from":"0x0","to":"0x64"},"senders":null,"transactionLookup":null,"receipts":{"from":"0x5a","to":"0x64"},"accountHistory":null,"storageHistory":null}"#
+        );
+        assert_eq!(serde_json::from_str::<HistoryRanges>(&s).unwrap(), ranges);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::result::ToRpcResult;
+use crate::result::{invalid_params_rpc_err, ToRpcResult};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_network_api::{NetworkInfo, PeerKind, Peers};
+use reth_network_api::{AccessRule, NetworkInfo, PeerAccessList, PeerKind, Peers};
 use reth_primitives::NodeRecord;
 use reth_rpc_api::AdminApiServer;
 use reth_rpc_types::{NodeInfo, PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo};
@@ -87,6 +87,36 @@ where
         Ok(NodeInfo::new(enr, status))
     }
 
+    /// Handler for `admin_addAccessRule`
+    async fn add_access_rule(&self, rule: String) -> RpcResult<bool> {
+        let rule =
+            rule.parse::<AccessRule>().map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.network.add_access_rule(rule).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_removeAccessRule`
+    async fn remove_access_rule(&self, rule: String) -> RpcResult<bool> {
+        let rule =
+            rule.parse::<AccessRule>().map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.network.remove_access_rule(rule).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_accessList`
+    async fn access_list(&self) -> RpcResult<Vec<String>> {
+        let access_list = self.network.access_list().await.to_rpc_result()?;
+        Ok(access_list.rules().iter().map(ToString::to_string).collect())
+    }
+
+    /// Handler for `admin_importAccessList`
+    async fn import_access_list(&self, rules: Vec<String>) -> RpcResult<bool> {
+        let rules = rules
+            .iter()
+            .map(|rule| rule.parse::<AccessRule>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.network.set_access_list(PeerAccessList::new(rules)).await.to_rpc_result()
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -8,6 +8,7 @@ use crate::{
         EthTransactions, TransactionSource,
     },
     result::{internal_rpc_err, ToRpcResult},
+    trace_cache::{TraceCache, TraceCacheKey},
     EthApiSpec, TracingCallGuard,
 };
 use async_trait::async_trait;
@@ -15,7 +16,7 @@ use jsonrpsee::core::RpcResult;
 use reth_primitives::{
     Account, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSigned, H160, H256,
 };
-use reth_provider::{BlockReaderIdExt, HeaderProvider, StateProviderBox};
+use reth_provider::{BlockNumReader, BlockReaderIdExt, HeaderProvider, StateProviderBox};
 use reth_revm::{
     database::{StateProviderDatabase, SubState},
     env::tx_env_with_recovered,
@@ -51,6 +52,8 @@ use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Provider, Eth> {
     inner: Arc<DebugApiInner<Provider, Eth>>,
+    /// Optional cache for the traces of recent blocks.
+    trace_cache: Option<TraceCache>,
 }
 
 // === impl DebugApi ===
@@ -65,7 +68,13 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
     ) -> Self {
         let inner =
             Arc::new(DebugApiInner { provider, eth_api: eth, task_spawner, tracing_call_guard });
-        Self { inner }
+        Self { inner, trace_cache: None }
+    }
+
+    /// Configures the [TraceCache] for the traces of recent blocks.
+    pub fn with_trace_cache(mut self, trace_cache: Option<TraceCache>) -> Self {
+        self.trace_cache = trace_cache;
+        self
     }
 }
 
@@ -140,6 +149,9 @@ where
     }
 
     /// Replays a block and returns the trace of each transaction.
+    ///
+    /// If a [TraceCache] is configured, the traces of recent blocks are served from the cache.
+    /// The pending block is never cached.
     pub async fn debug_trace_block(
         &self,
         block_id: BlockId,
@@ -157,11 +169,53 @@ where
         )?;
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        let cached = if block_id.is_pending() {
+            None
+        } else {
+            self.trace_cache_key(block.number, block.hash, &opts)?
+        };
+        if let Some((cache, key)) = &cached {
+            if let Some(traces) = cache.get(key).await {
+                return Ok(traces)
+            }
+        }
+
         // we need to get the state of the parent block because we're replaying this block on top of
         // its parent block's state
         let state_at = block.parent_hash;
 
-        self.trace_block_with(state_at.into(), block.body, cfg, block_env, opts).await
+        let traces =
+            self.trace_block_with(state_at.into(), block.body, cfg, block_env, opts).await?;
+        if let Some((cache, key)) = cached {
+            cache.insert(key, traces.clone());
+        }
+        Ok(traces)
+    }
+
+    /// Returns the [TraceCache] and the key for the traces of the given block, if they should be
+    /// cached.
+    fn trace_cache_key(
+        &self,
+        block_number: u64,
+        block_hash: H256,
+        opts: &GethDebugTracingOptions,
+    ) -> EthResult<Option<(&TraceCache, TraceCacheKey)>> {
+        let Some(cache) = self.trace_cache.as_ref() else { return Ok(None) };
+
+        // the results of custom JS tracers can't be restored losslessly
+        if matches!(opts.tracer, Some(GethDebugTracerType::JsTracer(_))) {
+            return Ok(None)
+        }
+
+        let tip = self.inner.provider.best_block_number()?;
+        if !cache.is_cacheable(block_number, tip) {
+            return Ok(None)
+        }
+
+        // the timeout does not affect the traces
+        let opts = GethDebugTracingOptions { timeout: None, ..opts.clone() };
+        Ok(Some((cache, TraceCacheKey::new(block_hash, "debug_traceBlock", &opts))))
     }
 
     /// Trace the transaction according to the provided options.
@@ -360,7 +414,7 @@ where
         let opts = opts.unwrap_or_default();
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
         let GethDebugTracingCallOptions { tracing_options, mut state_overrides, .. } = opts;
-        let gas_limit = self.inner.eth_api.call_gas_limit();
+        let limits = self.inner.eth_api.call_limits();
 
         // we're essentially replaying the transactions in the block here, hence we need the state
         // that points to the beginning of the block, which is the state at the parent block
@@ -414,7 +468,7 @@ where
                             cfg.clone(),
                             block_env.clone(),
                             tx,
-                            gas_limit,
+                            limits,
                             &mut db,
                             overrides,
                         )?;
@@ -998,7 +1052,7 @@ impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), trace_cache: self.trace_cache.clone() }
     }
 }
 
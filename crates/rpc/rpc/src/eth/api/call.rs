@@ -4,8 +4,8 @@ use crate::{
     eth::{
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
         revm_utils::{
-            build_call_evm_env, caller_gas_allowance, cap_tx_gas_limit_with_caller_allowance,
-            get_precompiles, inspect, prepare_call_env, transact, EvmOverrides,
+            build_call_evm_env, caller_gas_allowance, get_precompiles, inspect,
+            prepare_access_list_env, prepare_call_env, transact, EvmOverrides,
         },
         EthTransactions,
     },
@@ -15,7 +15,8 @@ use ethers_core::utils::get_contract_address;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{AccessList, BlockId, BlockNumberOrTag, Bytes, U256};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, PruneCheckpointReader, StateProvider,
+    StateProviderFactory,
 };
 use reth_revm::{
     access_list::AccessListInspector,
@@ -40,8 +41,12 @@ const MIN_CREATE_GAS: u64 = 53_000u64;
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
     Pool: TransactionPool + Clone + 'static,
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + 'static,
     Network: NetworkInfo + Send + Sync + 'static,
 {
     /// Estimate gas needed for execution of the `request` at the [BlockId].
@@ -94,7 +99,7 @@ where
             futures::try_join!(self.evm_env_at(target_block), self.block_by_id(target_block))?;
 
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
-        let gas_limit = self.inner.gas_cap;
+        let limits = self.call_limits;
 
         // we're essentially replaying the transactions in the block here, hence we need the state
         // that points to the beginning of the block, which is the state at the parent block
@@ -139,7 +144,7 @@ where
                     cfg.clone(),
                     block_env.clone(),
                     tx,
-                    gas_limit,
+                    limits,
                     &mut db,
                     overrides,
                 )?;
@@ -188,14 +193,18 @@ where
         // <https://github.com/ethereum/go-ethereum/blob/ee8e83fa5f6cb261dad2ed0a7bbcde4930c41e6c/internal/ethapi/api.go#L985>
         cfg.disable_base_fee = true;
 
+        self.call_limits.apply_to_cfg(&mut cfg);
+
         // keep a copy of gas related request values
         let request_gas = request.gas;
         let request_gas_price = request.gas_price;
-        let env_gas_limit = block.gas_limit;
+        // the gas limit to re-execute a failed request with, see `map_out_of_gas_err`
+        let env_gas_limit = block.gas_limit.min(U256::from(self.call_limits.gas_cap));
 
         // get the highest possible gas limit, either the request's set value or the currently
-        // configured gas limit
-        let mut highest_gas_limit = request.gas.unwrap_or(block.gas_limit);
+        // configured gas limit, capped by the configured gas cap
+        let mut highest_gas_limit =
+            request.gas.unwrap_or(block.gas_limit).min(U256::from(self.call_limits.gas_cap));
 
         // Configure the evm env
         let mut env = build_call_evm_env(cfg, block, request)?;
@@ -234,6 +243,8 @@ where
         // if the provided gas limit is less than computed cap, use that
         let gas_limit = std::cmp::min(U256::from(env.tx.gas_limit), highest_gas_limit);
         env.block.gas_limit = gas_limit;
+        // the gas limit of the request may exceed the gas cap, so it's clamped as well
+        env.tx.gas_limit = gas_limit.try_into().unwrap_or(u64::MAX);
 
         trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
 
@@ -347,23 +358,8 @@ where
         let (cfg, block, at) = self.evm_env_at(block_id).await?;
         let state = self.state_at(at)?;
 
-        let mut env = build_call_evm_env(cfg, block, request.clone())?;
-
-        // we want to disable this in eth_createAccessList, since this is common practice used by
-        // other node impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
-        env.cfg.disable_block_gas_limit = true;
-
-        // The basefee should be ignored for eth_createAccessList
-        // See:
-        // <https://github.com/ethereum/go-ethereum/blob/8990c92aea01ca07801597b00c0d83d4e2d9b811/internal/ethapi/api.go#L1476-L1476>
-        env.cfg.disable_base_fee = true;
-
         let mut db = SubState::new(StateProviderDatabase::new(state));
-
-        if request.gas.is_none() && env.tx.gas_price > U256::ZERO {
-            // no gas limit was provided in the request, so we need to cap the request's gas limit
-            cap_tx_gas_limit_with_caller_allowance(&mut db, &mut env.tx)?;
-        }
+        let env = prepare_access_list_env(cfg, block, request.clone(), self.call_limits, &mut db)?;
 
         let from = request.from.unwrap_or_default();
         let to = if let Some(to) = request.to {
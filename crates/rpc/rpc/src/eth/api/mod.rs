@@ -17,12 +17,14 @@ use reth_primitives::{
     Address, BlockId, BlockNumberOrTag, ChainInfo, SealedBlock, H256, U256, U64,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, PruneCheckpointReader, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::{BlockEnv, CfgEnv};
+use serde::{Deserialize, Serialize};
 use std::{
     future::Future,
     sync::Arc,
@@ -77,6 +79,8 @@ pub trait EthApiSpec: EthTransactions + Send + Sync {
 pub struct EthApi<Provider, Pool, Network> {
     /// All nested fields bundled together.
     inner: Arc<EthApiInner<Provider, Pool, Network>>,
+    /// Limits applied to `eth_call` and adjacent calls served by this handle.
+    call_limits: CallLimits,
 }
 
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
@@ -132,13 +136,20 @@ where
             signers: Default::default(),
             eth_cache,
             gas_oracle,
-            gas_cap,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
             tracing_call_pool,
         };
-        Self { inner: Arc::new(inner) }
+        Self { inner: Arc::new(inner), call_limits: CallLimits::new(gas_cap) }
+    }
+
+    /// Returns a new handle to the same instance that applies the given [CallLimits].
+    ///
+    /// This can be used to serve the same `eth_` API with different limits, for example with
+    /// unlimited calls on a local-only transport and capped calls on a public one.
+    pub fn with_call_limits(&self, call_limits: CallLimits) -> Self {
+        Self { inner: Arc::clone(&self.inner), call_limits }
     }
 
     /// Executes the future on a new blocking task.
@@ -173,7 +184,7 @@ where
 
     /// Returns the configured gas limit cap for `eth_call` and tracing related calls
     pub fn gas_cap(&self) -> u64 {
-        self.inner.gas_cap
+        self.call_limits.gas_cap
     }
 
     /// Returns the inner `Provider`
@@ -326,7 +337,7 @@ impl<Provider, Pool, Events> std::fmt::Debug for EthApi<Provider, Pool, Events>
 
 impl<Provider, Pool, Events> Clone for EthApi<Provider, Pool, Events> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), call_limits: self.call_limits }
     }
 }
 
@@ -334,8 +345,12 @@ impl<Provider, Pool, Events> Clone for EthApi<Provider, Pool, Events> {
 impl<Provider, Pool, Network> EthApiSpec for EthApi<Provider, Pool, Network>
 where
     Pool: TransactionPool + Clone + 'static,
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + 'static,
     Network: NetworkInfo + 'static,
 {
     /// Returns the current ethereum protocol version.
@@ -413,6 +428,69 @@ impl From<GasCap> for u64 {
     }
 }
 
+/// The default EVM memory limit in bytes for `eth_call` and adjacent calls.
+///
+/// This matches the default limit of the EVM itself.
+pub const RPC_DEFAULT_MEMORY_LIMIT: u64 = (1 << 32) - 1;
+
+/// Limits applied to `eth_call`, `eth_estimateGas` and call simulation/tracing RPC methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallLimits {
+    /// Maximum gas limit of a single call.
+    pub gas_cap: u64,
+    /// Maximum size in bytes of the code in a state override, `None` for no limit.
+    pub max_code_size: Option<usize>,
+    /// Maximum EVM memory in bytes a single call may allocate, `None` for no limit.
+    pub memory_limit: Option<u64>,
+}
+
+impl CallLimits {
+    /// Creates the default limits with the given gas cap.
+    pub fn new(gas_cap: u64) -> Self {
+        Self { gas_cap, max_code_size: None, memory_limit: Some(RPC_DEFAULT_MEMORY_LIMIT) }
+    }
+
+    /// Limits that only restrict the gas of a call to the given gas cap.
+    ///
+    /// The code size and memory limits are lifted. The gas cap is kept, because it is what bounds
+    /// the execution time of a call, including the cost of the memory it allocates.
+    ///
+    /// This should only be used for trusted origins, like local-only transports.
+    pub const fn trusted(gas_cap: u64) -> Self {
+        Self { gas_cap, max_code_size: None, memory_limit: None }
+    }
+
+    /// Sets the gas cap.
+    pub const fn with_gas_cap(mut self, gas_cap: u64) -> Self {
+        self.gas_cap = gas_cap;
+        self
+    }
+
+    /// Sets the maximum code size of state overrides.
+    pub const fn with_max_code_size(mut self, max_code_size: Option<usize>) -> Self {
+        self.max_code_size = max_code_size;
+        self
+    }
+
+    /// Sets the EVM memory limit.
+    pub const fn with_memory_limit(mut self, memory_limit: Option<u64>) -> Self {
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    /// Applies the memory limit to the given [CfgEnv].
+    pub(crate) fn apply_to_cfg(&self, cfg: &mut CfgEnv) {
+        cfg.memory_limit = self.memory_limit.unwrap_or(u64::MAX);
+    }
+}
+
+impl Default for CallLimits {
+    fn default() -> Self {
+        Self::new(RPC_DEFAULT_GAS_CAP.into())
+    }
+}
+
 /// Container type `EthApi`
 struct EthApiInner<Provider, Pool, Network> {
     /// The transaction pool.
@@ -427,8 +505,6 @@ struct EthApiInner<Provider, Pool, Network> {
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
     gas_oracle: GasPriceOracle<Provider>,
-    /// Maximum gas limit for `eth_call` and call tracing RPC methods.
-    gas_cap: u64,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
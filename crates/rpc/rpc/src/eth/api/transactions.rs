@@ -1,7 +1,7 @@
 //! Contains RPC handler implementations specific to transactions
 use crate::{
     eth::{
-        api::pending_block::PendingBlockEnv,
+        api::{pending_block::PendingBlockEnv, CallLimits},
         error::{EthApiError, EthResult, SignError},
         revm_utils::{
             inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
@@ -16,14 +16,15 @@ use reth_network_api::NetworkInfo;
 use reth_primitives::{
     eip4844::calc_blob_gasprice,
     Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock,
+    IntoRecoveredTransaction, PrunePart, Receipt, SealedBlock,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, H256, U128, U256, U64,
 };
 use reth_rpc_types_compat::from_recovered_with_block_context;
 
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, PruneCheckpointReader, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_revm::{
     database::{StateProviderDatabase, SubState},
@@ -54,6 +55,9 @@ pub trait EthTransactions: Send + Sync {
     /// Returns default gas limit to use for `eth_call` and tracing RPC methods.
     fn call_gas_limit(&self) -> u64;
 
+    /// Returns the [CallLimits] to apply to `eth_call` and tracing RPC methods.
+    fn call_limits(&self) -> CallLimits;
+
     /// Returns the state at the given [BlockId]
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox<'_>>;
 
@@ -241,12 +245,20 @@ pub trait EthTransactions: Send + Sync {
 impl<Provider, Pool, Network> EthTransactions for EthApi<Provider, Pool, Network>
 where
     Pool: TransactionPool + Clone + 'static,
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Provider: BlockReaderIdExt
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + 'static,
     Network: NetworkInfo + Send + Sync + 'static,
 {
     fn call_gas_limit(&self) -> u64 {
-        self.inner.gas_cap
+        self.call_limits.gas_cap
+    }
+
+    fn call_limits(&self) -> CallLimits {
+        self.call_limits
     }
 
     fn state_at(&self, at: BlockId) -> EthResult<StateProviderBox<'_>> {
@@ -350,6 +362,8 @@ where
                 self.pool().get(&hash).map(|tx| tx.transaction.to_recovered_transaction())
             {
                 resp = Some(TransactionSource::Pool(tx));
+            } else {
+                self.ensure_transaction_lookup(hash)?;
             }
         }
 
@@ -405,12 +419,20 @@ where
             .on_blocking_task(|this| async move {
                 let (tx, meta) = match this.provider().transaction_by_hash_with_meta(hash)? {
                     Some((tx, meta)) => (tx, meta),
-                    None => return Ok(None),
+                    None => {
+                        this.ensure_transaction_lookup(hash)?;
+                        return Ok(None)
+                    }
                 };
 
                 let receipt = match this.provider().receipt_by_hash(hash)? {
                     Some(recpt) => recpt,
-                    None => return Ok(None),
+                    None => {
+                        // the receipts of the block fail with the prune checkpoint if they were
+                        // pruned
+                        this.provider().receipts_by_block(meta.block_number.into())?;
+                        return Ok(None)
+                    }
                 };
 
                 Ok(Some((tx, meta, receipt)))
@@ -539,7 +561,7 @@ where
                     cfg,
                     block_env,
                     request,
-                    this.call_gas_limit(),
+                    this.call_limits(),
                     &mut db,
                     overrides,
                 )?;
@@ -707,6 +729,35 @@ where
     }
 }
 
+impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Provider: BlockReaderIdExt + ChainSpecProvider + PruneCheckpointReader + 'static,
+{
+    /// Returns [EthApiError::HistoryUnavailable] if the transaction lookup was pruned and the
+    /// transaction, which was not found on disk, is not in the pool either, since it may have been
+    /// mined in one of the pruned blocks.
+    fn ensure_transaction_lookup(&self, hash: H256) -> EthResult<()> {
+        if self.pool().contains(&hash) {
+            return Ok(())
+        }
+
+        let pruned = self
+            .provider()
+            .get_prune_checkpoint(PrunePart::TransactionLookup)?
+            .and_then(|checkpoint| checkpoint.block_number);
+        match pruned {
+            // the block of the transaction is unknown, so this reports the highest pruned block
+            Some(pruned) => Err(EthApiError::HistoryUnavailable {
+                part: PrunePart::TransactionLookup,
+                block: pruned,
+                lowest_available: pruned + 1,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
     Pool: TransactionPool + 'static,
@@ -6,7 +6,7 @@ use jsonrpsee::{
     types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject},
 };
 use reth_interfaces::RethError;
-use reth_primitives::{abi::decode_revert_reason, Address, Bytes, U256};
+use reth_primitives::{abi::decode_revert_reason, Address, BlockNumber, Bytes, PrunePart, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
 use reth_transaction_pool::error::{
@@ -42,6 +42,12 @@ pub enum EthApiError {
     UnknownBlockOrTxIndex,
     #[error("Invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the requested historical data is no longer available locally, e.g. because it
+    /// was pruned.
+    #[error(
+        "{part} at block #{block} is unavailable, lowest available block is #{lowest_available}"
+    )]
+    HistoryUnavailable { part: PrunePart, block: BlockNumber, lowest_available: BlockNumber },
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("Prevrandao not in th EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -61,6 +67,17 @@ pub enum EthApiError {
     /// conflicting `state` and `stateDiff` fields
     #[error("account {0:?} has both 'state' and 'stateDiff'")]
     BothStateAndStateDiffInOverride(Address),
+    /// Thrown when the code of an [AccountOverride](reth_rpc_types::state::AccountOverride)
+    /// exceeds the configured maximum code size
+    #[error("code override of account {account:?} is too large: {size} > {max_code_size}")]
+    OverrideCodeTooLarge {
+        /// The account with the code override
+        account: Address,
+        /// Size of the override code
+        size: usize,
+        /// The configured maximum code size
+        max_code_size: usize,
+    },
     /// Other internal error
     #[error(transparent)]
     Internal(RethError),
@@ -111,6 +128,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
+            EthApiError::OverrideCodeTooLarge { .. } |
             EthApiError::InvalidTracerConfig => invalid_params_rpc_err(error.to_string()),
             EthApiError::InvalidTransaction(err) => err.into(),
             EthApiError::PoolError(err) => err.into(),
@@ -125,6 +143,17 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::UnknownSafeOrFinalizedBlock => {
                 rpc_error_with_code(EthRpcErrorCode::UnknownBlock.code(), error.to_string())
             }
+            EthApiError::HistoryUnavailable { part, block, lowest_available } => {
+                ErrorObject::owned(
+                    EthRpcErrorCode::ResourceUnavailable.code(),
+                    error.to_string(),
+                    Some(serde_json::json!({
+                        "part": part.to_string(),
+                        "block": block,
+                        "lowestAvailable": lowest_available,
+                    })),
+                )
+            }
             EthApiError::Unsupported(msg) => internal_rpc_err(msg),
             EthApiError::InternalJsTracerError(msg) => internal_rpc_err(msg),
             EthApiError::InvalidParams(msg) => invalid_params_rpc_err(msg),
@@ -177,6 +206,24 @@ impl From<reth_interfaces::provider::ProviderError> for EthApiError {
             ProviderError::FinalizedBlockNotFound | ProviderError::SafeBlockNotFound => {
                 EthApiError::UnknownSafeOrFinalizedBlock
             }
+            ProviderError::HistoryUnavailable { part, block_number, lowest_available } => {
+                match part {
+                    // historical state providers are keyed by the block whose changes are not yet
+                    // applied, which is one past the block that was requested
+                    PrunePart::AccountHistory | PrunePart::StorageHistory => {
+                        EthApiError::HistoryUnavailable {
+                            part,
+                            block: block_number.saturating_sub(1),
+                            lowest_available: lowest_available.saturating_sub(1),
+                        }
+                    }
+                    part => EthApiError::HistoryUnavailable {
+                        part,
+                        block: block_number,
+                        lowest_available,
+                    },
+                }
+            }
             err => EthApiError::Internal(err.into()),
         }
     }
@@ -635,4 +682,37 @@ mod tests {
         let err = EthApiError::ExecutionTimedOut(Duration::from_secs(10));
         assert_eq!(err.to_string(), "execution aborted (timeout = 10s)");
     }
+
+    #[test]
+    fn history_unavailable_error() {
+        let err: EthApiError = reth_interfaces::provider::ProviderError::HistoryUnavailable {
+            part: PrunePart::AccountHistory,
+            block_number: 11,
+            lowest_available: 21,
+        }
+        .into();
+        assert_eq!(
+            err.to_string(),
+            "AccountHistory at block #10 is unavailable, lowest available block is #20"
+        );
+
+        let err = ErrorObject::from(err);
+        assert_eq!(err.code(), EthRpcErrorCode::ResourceUnavailable.code());
+        assert_eq!(
+            err.data().unwrap().get(),
+            r#"{"block":10,"lowestAvailable":20,"part":"AccountHistory"}"#
+        );
+
+        // other parts are keyed by the requested block
+        let err: EthApiError = reth_interfaces::provider::ProviderError::HistoryUnavailable {
+            part: PrunePart::Receipts,
+            block_number: 11,
+            lowest_available: 21,
+        }
+        .into();
+        assert_eq!(
+            err.to_string(),
+            "Receipts at block #11 is unavailable, lowest available block is #21"
+        );
+    }
 }
@@ -8,21 +8,35 @@ use crate::{
     EthSubscriptionIdProvider,
 };
 use async_trait::async_trait;
-use jsonrpsee::{core::RpcResult, server::IdProvider};
+use jsonrpsee::{
+    core::RpcResult,
+    server::{IdProvider, SubscriptionMessage},
+    PendingSubscriptionSink, SubscriptionSink,
+};
 use reth_interfaces::RethError;
-use reth_primitives::{BlockHashOrNumber, Receipt, SealedBlock};
+use reth_primitives::{BlockHashOrNumber, BlockNumberOrTag, Receipt, SealedBlock};
 use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider};
 use reth_rpc_api::EthFilterApiServer;
-use reth_rpc_types::{Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log};
+use reth_rpc_types::{
+    Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log, LogsPage,
+    LogsPageOptions, LogsPageToken,
+};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::TransactionPool;
 use std::{collections::HashMap, iter::StepBy, ops::RangeInclusive, sync::Arc, time::Instant};
 use tokio::sync::Mutex;
-use tracing::trace;
+use tracing::{debug, trace};
 
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// The maximum number of blocks scanned for a single page of a paginated log query.
+const MAX_BLOCKS_PER_LOGS_PAGE: u64 = 10_000;
+
+/// The maximum number of consecutive pages without logs that a logs pages subscription scans
+/// before it sends an empty page to report its progress.
+const MAX_EMPTY_LOGS_PAGES: usize = 10;
+
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool> {
     /// All nested fields bundled together.
@@ -51,6 +65,7 @@ impl<Provider, Pool> EthFilter<Provider, Pool> {
             max_logs_per_response,
             eth_cache,
             max_headers_range: MAX_HEADERS_RANGE,
+            max_blocks_per_logs_page: MAX_BLOCKS_PER_LOGS_PAGE,
             task_spawner,
         };
         Self { inner: Arc::new(inner) }
@@ -220,6 +235,34 @@ where
         trace!(target: "rpc::eth", "Serving eth_getLogs");
         Ok(self.inner.logs_for_filter(filter).await?)
     }
+
+    /// Returns a single page of logs matching given filter object.
+    ///
+    /// Handler for `eth_getLogsPage`
+    async fn logs_page(
+        &self,
+        filter: Filter,
+        options: Option<LogsPageOptions>,
+    ) -> RpcResult<LogsPage> {
+        trace!(target: "rpc::eth", "Serving eth_getLogsPage");
+        Ok(self.inner.logs_page_for_filter(&filter, options.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `eth_subscribeLogsPages`
+    async fn logs_pages(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: Filter,
+        limit: Option<u64>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving eth_subscribeLogsPages");
+        let sink = pending.accept().await?;
+        let inner = Arc::clone(&self.inner);
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = inner.stream_logs_pages(sink, filter, limit).await;
+        }));
+        Ok(())
+    }
 }
 
 impl<Provider, Pool> std::fmt::Debug for EthFilter<Provider, Pool> {
@@ -252,8 +295,9 @@ struct EthFilterInner<Provider, Pool> {
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
     max_headers_range: u64,
+    /// maximum number of blocks to scan for a single page of logs
+    max_blocks_per_logs_page: u64,
     /// The type that can spawn tasks.
-    #[allow(unused)]
     task_spawner: Box<dyn TaskSpawner>,
 }
 
@@ -283,26 +327,135 @@ where
                 Ok(all_logs)
             }
             FilterBlockOption::Range { from_block, to_block } => {
-                // compute the range
-                let info = self.provider.chain_info()?;
-
-                // we start at the most recent block if unset in filter
-                let start_block = info.best_number;
-                let from = from_block
-                    .map(|num| self.provider.convert_block_number(num))
-                    .transpose()?
-                    .flatten();
-                let to = to_block
-                    .map(|num| self.provider.convert_block_number(num))
-                    .transpose()?
-                    .flatten();
                 let (from_block_number, to_block_number) =
-                    logs_utils::get_filter_block_range(from, to, start_block, info);
+                    self.filter_block_range(from_block, to_block)?;
                 self.get_logs_in_block_range(&filter, from_block_number, to_block_number).await
             }
         }
     }
 
+    /// Computes the _inclusive_ block range of a range filter.
+    fn filter_block_range(
+        &self,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> Result<(u64, u64), FilterError> {
+        let info = self.provider.chain_info()?;
+
+        // we start at the most recent block if unset in filter
+        let start_block = info.best_number;
+        let from =
+            from_block.map(|num| self.provider.convert_block_number(num)).transpose()?.flatten();
+        let to = to_block.map(|num| self.provider.convert_block_number(num)).transpose()?.flatten();
+        Ok(logs_utils::get_filter_block_range(from, to, start_block, info))
+    }
+
+    /// Returns a single page of logs matching given filter object.
+    ///
+    /// If the options contain a page token, the query resumes at the position of the token, which
+    /// must lie inside the range of the filter. Otherwise the query starts at the beginning of the
+    /// range.
+    async fn logs_page_for_filter(
+        &self,
+        filter: &Filter,
+        options: LogsPageOptions,
+    ) -> Result<LogsPage, FilterError> {
+        // the page size is always capped by the configured response limit
+        let limit = options
+            .limit
+            .map(|limit| limit.min(self.max_logs_per_response as u64) as usize)
+            .unwrap_or(self.max_logs_per_response)
+            .max(1);
+
+        let (from_block, to_block, min_from_block) = match filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                match self.provider.block_number(block_hash)? {
+                    Some(number) => (number, number, number),
+                    None => return Ok(LogsPage::default()),
+                }
+            }
+            FilterBlockOption::Range { from_block: from, to_block: to } => {
+                let (from_block, to_block) = self.filter_block_range(from, to)?;
+                // tags like `latest` move forward, so a token may start before the block the tag
+                // resolves to now
+                let min_from_block = match from {
+                    Some(BlockNumberOrTag::Number(_)) => from_block,
+                    _ => 0,
+                };
+                (from_block, to_block, min_from_block)
+            }
+        };
+
+        let (from_block, to_block, start_log_index) = match options.page_token {
+            Some(token) => {
+                if !is_page_token_in_range(&token, min_from_block, to_block) {
+                    return Err(FilterError::InvalidPageToken)
+                }
+                (token.block_number, token.to_block, token.log_index)
+            }
+            None => (from_block, to_block, 0),
+        };
+
+        self.get_logs_page_in_block_range(filter, from_block, to_block, start_log_index, limit)
+            .await
+    }
+
+    /// Sends all logs matching the filter to the subscription sink, page by page.
+    ///
+    /// Pages without logs that only advance the scanned range are not sent, unless
+    /// [MAX_EMPTY_LOGS_PAGES] of them follow each other. Then an empty page is sent to report the
+    /// progress, which also applies the backpressure of the client. This stops early if the
+    /// subscription is closed. If a page can't be fetched, a last page with the error and the
+    /// position to resume at is sent.
+    async fn stream_logs_pages(
+        &self,
+        sink: SubscriptionSink,
+        filter: Filter,
+        limit: Option<u64>,
+    ) -> Result<(), jsonrpsee::core::Error> {
+        let mut options = LogsPageOptions { page_token: None, limit };
+        let mut empty_pages = 0;
+        loop {
+            if sink.is_closed() {
+                return Ok(())
+            }
+
+            let page = match self.logs_page_for_filter(&filter, options.clone()).await {
+                Ok(page) => page,
+                Err(err) => {
+                    debug!(target: "rpc::eth::filter", %err, "failed to fetch logs page");
+                    let page = LogsPage {
+                        logs: Vec::new(),
+                        next_page_token: options.page_token,
+                        error: Some(err.to_string()),
+                    };
+                    let _ = sink.send(SubscriptionMessage::from_json(&page)?).await;
+                    return Ok(())
+                }
+            };
+            let next_page_token = page.next_page_token;
+
+            if page.logs.is_empty() && next_page_token.is_some() {
+                empty_pages += 1;
+                if empty_pages < MAX_EMPTY_LOGS_PAGES {
+                    options.page_token = next_page_token;
+                    continue
+                }
+            }
+            empty_pages = 0;
+
+            let msg = SubscriptionMessage::from_json(&page)?;
+            if sink.send(msg).await.is_err() {
+                return Ok(())
+            }
+
+            match next_page_token {
+                Some(token) => options.page_token = Some(token),
+                None => return Ok(()),
+            }
+        }
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
@@ -400,6 +553,162 @@ where
 
         Ok(all_logs)
     }
+
+    /// Returns at most `limit` logs in the given _inclusive_ range that match the filter, skipping
+    /// all logs of the first block with an index lower than `start_log_index`.
+    ///
+    /// At most `max_blocks_per_logs_page` blocks are scanned, so a page may contain fewer logs than
+    /// the limit, or none at all, even though the range is not exhausted. In both cases the
+    /// returned page contains the token of the next position.
+    async fn get_logs_page_in_block_range(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        start_log_index: u64,
+        limit: usize,
+    ) -> Result<LogsPage, FilterError> {
+        trace!(target: "rpc::eth::filter", from=from_block, to=to_block, start_log_index, limit, ?filter, "finding logs page in range");
+
+        let mut page = LogsPageCollector::new(from_block, to_block, start_log_index, limit);
+        if from_block > to_block {
+            return Ok(page.finish(None))
+        }
+
+        let filter_params = FilteredParams::new(Some(filter.clone()));
+
+        // derive bloom filters from filter input
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let scan_to = page.scan_to(self.max_blocks_per_logs_page);
+        for (from, to) in BlockRangeInclusiveIter::new(from_block..=scan_to, self.max_headers_range)
+        {
+            let headers = self.provider.headers_range(from..=to)?;
+
+            for (idx, header) in headers.iter().enumerate() {
+                if !FilteredParams::matches_address(header.logs_bloom, &address_filter) ||
+                    !FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+                {
+                    continue
+                }
+
+                // these are consecutive headers, so we can use the parent hash of the next block to
+                // get the current header's hash
+                let num_hash: BlockHashOrNumber = headers
+                    .get(idx + 1)
+                    .map(|h| h.parent_hash.into())
+                    .unwrap_or_else(|| header.number.into());
+
+                let Some((block, receipts)) = self.block_and_receipts_by_number(num_hash).await?
+                else {
+                    continue
+                };
+
+                let block_logs = logs_utils::matching_block_logs(
+                    &filter_params,
+                    (block.number, block.hash).into(),
+                    block.body.into_iter().map(|tx| tx.hash()).zip(receipts),
+                    false,
+                );
+                if let Some(next_page_token) = page.push_block(block.number, block_logs) {
+                    return Ok(page.finish(Some(next_page_token)))
+                }
+            }
+        }
+
+        let next_page_token = page.scan_boundary(scan_to);
+        Ok(page.finish(next_page_token))
+    }
+}
+
+/// Collects the matching logs of consecutive blocks into a single [LogsPage].
+#[derive(Debug)]
+struct LogsPageCollector {
+    /// First block of the page.
+    from_block: u64,
+    /// Inclusive upper bound of the paginated range, pinned in all tokens.
+    to_block: u64,
+    /// Index of the first log of `from_block` that belongs to the page.
+    start_log_index: u64,
+    /// Max number of logs of the page.
+    limit: usize,
+    /// Collected logs.
+    logs: Vec<Log>,
+}
+
+impl LogsPageCollector {
+    fn new(from_block: u64, to_block: u64, start_log_index: u64, limit: usize) -> Self {
+        Self { from_block, to_block, start_log_index, limit, logs: Vec::new() }
+    }
+
+    /// Returns the last block to scan for this page, if at most `max_blocks` blocks are scanned.
+    fn scan_to(&self, max_blocks: u64) -> u64 {
+        self.from_block.saturating_add(max_blocks.max(1) - 1).min(self.to_block)
+    }
+
+    /// Adds the matching logs of the next block to the page.
+    ///
+    /// Returns the token of the next page if the page is full.
+    fn push_block(&mut self, block_number: u64, mut block_logs: Vec<Log>) -> Option<LogsPageToken> {
+        if block_number == self.from_block && self.start_log_index > 0 {
+            block_logs.retain(|log| page_log_index(log) >= self.start_log_index);
+        }
+
+        let remaining = self.limit - self.logs.len();
+        if block_logs.len() > remaining {
+            // page is full, resume at the first log that didn't fit
+            let next_page_token = LogsPageToken {
+                block_number,
+                log_index: page_log_index(&block_logs[remaining]),
+                to_block: self.to_block,
+            };
+            block_logs.truncate(remaining);
+            self.logs.extend(block_logs);
+            return Some(next_page_token)
+        }
+
+        self.logs.extend(block_logs);
+        if self.logs.len() == self.limit && block_number < self.to_block {
+            return Some(LogsPageToken {
+                block_number: block_number + 1,
+                log_index: 0,
+                to_block: self.to_block,
+            })
+        }
+        None
+    }
+
+    /// Returns the token of the next page if the scan stopped at `scan_to` before the end of the
+    /// range.
+    fn scan_boundary(&self, scan_to: u64) -> Option<LogsPageToken> {
+        (scan_to < self.to_block).then_some(LogsPageToken {
+            block_number: scan_to + 1,
+            log_index: 0,
+            to_block: self.to_block,
+        })
+    }
+
+    fn finish(self, next_page_token: Option<LogsPageToken>) -> LogsPage {
+        LogsPage { logs: self.logs, next_page_token, error: None }
+    }
+}
+
+/// Returns true if the token resumes a query inside the given range.
+///
+/// The token must not start before `min_from_block` and its pinned upper bound must not exceed
+/// `to_block`.
+fn is_page_token_in_range(token: &LogsPageToken, min_from_block: u64, to_block: u64) -> bool {
+    min_from_block <= token.block_number &&
+        token.block_number <= token.to_block &&
+        token.to_block <= to_block
+}
+
+/// Returns the index of the log within its block.
+///
+/// All logs returned by [logs_utils::matching_block_logs] have this set.
+fn page_log_index(log: &Log) -> u64 {
+    log.log_index.map(|idx| idx.saturating_to()).unwrap_or_default()
 }
 
 /// All active filters
@@ -435,6 +744,9 @@ pub enum FilterError {
     QueryExceedsMaxResults(usize),
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
+    /// Error thrown when a page token does not belong to the range of the filter.
+    #[error("invalid page token")]
+    InvalidPageToken,
     /// Error thrown when a spawned task failed to deliver a response.
     #[error("internal filter error")]
     InternalError,
@@ -452,7 +764,7 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             FilterError::EthAPIError(err) => err.into(),
-            err @ FilterError::QueryExceedsMaxResults(_) => {
+            err @ (FilterError::QueryExceedsMaxResults(_) | FilterError::InvalidPageToken) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
         }
@@ -496,6 +808,7 @@ impl Iterator for BlockRangeInclusiveIter {
 mod tests {
     use super::*;
     use rand::{thread_rng, Rng};
+    use reth_primitives::U256;
 
     #[test]
     fn test_block_range_iter() {
@@ -519,4 +832,98 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    fn block_logs(block_number: u64, log_indices: impl IntoIterator<Item = u64>) -> Vec<Log> {
+        log_indices
+            .into_iter()
+            .map(|log_index| Log {
+                address: Default::default(),
+                topics: Vec::new(),
+                data: Default::default(),
+                block_hash: None,
+                block_number: Some(U256::from(block_number)),
+                transaction_hash: None,
+                transaction_index: None,
+                log_index: Some(U256::from(log_index)),
+                removed: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_token_in_range() {
+        let token = LogsPageToken { block_number: 10, log_index: 3, to_block: 20 };
+        assert!(is_page_token_in_range(&token, 0, 20));
+        assert!(is_page_token_in_range(&token, 10, 30));
+        // starts before the range
+        assert!(!is_page_token_in_range(&token, 11, 20));
+        // ends after the range
+        assert!(!is_page_token_in_range(&token, 0, 19));
+        // starts after its own upper bound
+        let token = LogsPageToken { block_number: 21, log_index: 0, to_block: 20 };
+        assert!(!is_page_token_in_range(&token, 0, 20));
+    }
+
+    #[test]
+    fn logs_page_resumes_mid_block() {
+        let mut page = LogsPageCollector::new(10, 20, 0, 3);
+        let token = page.push_block(10, block_logs(10, 0..5)).unwrap();
+        assert_eq!(token, LogsPageToken { block_number: 10, log_index: 3, to_block: 20 });
+        assert_eq!(page.finish(Some(token)).logs, block_logs(10, 0..3));
+
+        // the next page skips the logs of the first block that were already returned
+        let mut page =
+            LogsPageCollector::new(token.block_number, token.to_block, token.log_index, 3);
+        assert_eq!(page.push_block(10, block_logs(10, 0..5)), None);
+        let token = page.push_block(11, block_logs(11, 0..2)).unwrap();
+        assert_eq!(token, LogsPageToken { block_number: 11, log_index: 1, to_block: 20 });
+        let logs = page.finish(Some(token)).logs;
+        assert_eq!(logs, [block_logs(10, 3..5), block_logs(11, 0..1)].concat());
+    }
+
+    #[test]
+    fn logs_page_full_at_end_of_block() {
+        // page is exactly full at the end of a block, resume at the next block
+        let mut page = LogsPageCollector::new(10, 20, 0, 4);
+        assert_eq!(page.push_block(10, block_logs(10, 0..2)), None);
+        let token = page.push_block(12, block_logs(12, 0..2)).unwrap();
+        assert_eq!(token, LogsPageToken { block_number: 13, log_index: 0, to_block: 20 });
+        assert_eq!(page.finish(Some(token)).logs.len(), 4);
+
+        // page is exactly full at the end of the range, no next page
+        let mut page = LogsPageCollector::new(10, 20, 0, 4);
+        assert_eq!(page.push_block(20, block_logs(20, 0..4)), None);
+        assert_eq!(page.scan_boundary(20), None);
+    }
+
+    #[test]
+    fn logs_page_pins_to_block() {
+        let mut page = LogsPageCollector::new(0, 100, 0, 1);
+        let token = page.push_block(5, block_logs(5, 0..2)).unwrap();
+        assert_eq!(token.to_block, 100);
+        let token = page.scan_boundary(50).unwrap();
+        assert_eq!(token, LogsPageToken { block_number: 51, log_index: 0, to_block: 100 });
+    }
+
+    #[test]
+    fn logs_page_caps_scanned_blocks() {
+        let page = LogsPageCollector::new(100, 1_000_000, 0, 10);
+        let scan_to = page.scan_to(MAX_BLOCKS_PER_LOGS_PAGE);
+        assert_eq!(scan_to, 100 + MAX_BLOCKS_PER_LOGS_PAGE - 1);
+
+        // an empty page still advances to the end of the scanned range
+        let next_page_token = page.scan_boundary(scan_to);
+        let page = page.finish(next_page_token);
+        assert!(page.logs.is_empty());
+        assert_eq!(
+            page.next_page_token,
+            Some(LogsPageToken { block_number: scan_to + 1, log_index: 0, to_block: 1_000_000 })
+        );
+
+        let page = LogsPageCollector::new(100, 200, 0, 10);
+        let scan_to = page.scan_to(MAX_BLOCKS_PER_LOGS_PAGE);
+        assert_eq!(scan_to, 200);
+        assert_eq!(page.scan_boundary(scan_to), None);
+        assert_eq!(LogsPageCollector::new(u64::MAX - 1, u64::MAX, 0, 1).scan_to(100), u64::MAX);
+    }
 }
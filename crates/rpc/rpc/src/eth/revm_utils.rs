@@ -1,6 +1,9 @@
 //! utilities for working with revm
 
-use crate::eth::error::{EthApiError, EthResult, RpcInvalidTransactionError};
+use crate::eth::{
+    api::CallLimits,
+    error::{EthApiError, EthResult, RpcInvalidTransactionError},
+};
 use reth_primitives::{
     AccessList, Address, TransactionSigned, TransactionSignedEcRecovered, TxHash, H256, U256,
 };
@@ -181,12 +184,15 @@ where
 
 /// Prepares the [Env] for execution.
 ///
+/// The gas cap and the memory limit of the given [CallLimits] are applied to the [Env], the
+/// maximum code size is enforced for the state overrides.
+///
 /// Does not commit any changes to the underlying database.
 pub(crate) fn prepare_call_env<DB>(
     mut cfg: CfgEnv,
     block: BlockEnv,
     request: CallRequest,
-    gas_limit: u64,
+    limits: CallLimits,
     db: &mut CacheDB<DB>,
     overrides: EvmOverrides,
 ) -> EthResult<Env>
@@ -207,13 +213,15 @@ where
     // <https://github.com/ethereum/go-ethereum/blob/ee8e83fa5f6cb261dad2ed0a7bbcde4930c41e6c/internal/ethapi/api.go#L985>
     cfg.disable_base_fee = true;
 
+    limits.apply_to_cfg(&mut cfg);
+
     let request_gas = request.gas;
 
     let mut env = build_call_evm_env(cfg, block, request)?;
 
     // apply state overrides
     if let Some(state_overrides) = overrides.state {
-        apply_state_overrides(state_overrides, db)?;
+        apply_state_overrides(state_overrides, limits.max_code_size, db)?;
     }
 
     // apply block overrides
@@ -239,10 +247,56 @@ where
             // <https://github.com/ledgerwatch/erigon/blob/eae2d9a79cb70dbe30b3a6b79c436872e4605458/cmd/rpcdaemon/commands/trace_adhoc.go#L956
             // https://github.com/ledgerwatch/erigon/blob/eae2d9a79cb70dbe30b3a6b79c436872e4605458/eth/ethconfig/config.go#L94>
             trace!(target: "rpc::eth::call", ?env, "Applying gas limit cap as the maximum gas limit");
-            env.tx.gas_limit = gas_limit;
+            env.tx.gas_limit = limits.gas_cap;
         }
     }
 
+    // Neither the gas limit of the request nor the caller allowance may exceed the configured gas
+    // cap
+    env.tx.gas_limit = env.tx.gas_limit.min(limits.gas_cap);
+
+    Ok(env)
+}
+
+/// Prepares the [Env] for `eth_createAccessList`.
+///
+/// The gas cap and the memory limit of the given [CallLimits] are applied to the [Env].
+///
+/// Does not commit any changes to the underlying database.
+pub(crate) fn prepare_access_list_env<DB>(
+    cfg: CfgEnv,
+    block: BlockEnv,
+    request: CallRequest,
+    limits: CallLimits,
+    db: &mut CacheDB<DB>,
+) -> EthResult<Env>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    let request_gas = request.gas;
+
+    let mut env = build_call_evm_env(cfg, block, request)?;
+
+    limits.apply_to_cfg(&mut env.cfg);
+
+    // we want to disable this in eth_createAccessList, since this is common practice used by
+    // other node impls and providers <https://github.com/foundry-rs/foundry/issues/4388>
+    env.cfg.disable_block_gas_limit = true;
+
+    // The basefee should be ignored for eth_createAccessList
+    // See:
+    // <https://github.com/ethereum/go-ethereum/blob/8990c92aea01ca07801597b00c0d83d4e2d9b811/internal/ethapi/api.go#L1476-L1476>
+    env.cfg.disable_base_fee = true;
+
+    if request_gas.is_none() && env.tx.gas_price > U256::ZERO {
+        // no gas limit was provided in the request, so we need to cap the request's gas limit
+        cap_tx_gas_limit_with_caller_allowance(db, &mut env.tx)?;
+    }
+
+    // applied last, so the caller allowance can't exceed the gas cap either
+    env.tx.gas_limit = env.tx.gas_limit.min(limits.gas_cap);
+
     Ok(env)
 }
 
@@ -500,12 +554,27 @@ fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
 }
 
 /// Applies the given state overrides (a set of [AccountOverride]) to the [CacheDB].
-fn apply_state_overrides<DB>(overrides: StateOverride, db: &mut CacheDB<DB>) -> EthResult<()>
+///
+/// Fails if the code of an override exceeds `max_code_size`.
+fn apply_state_overrides<DB>(
+    overrides: StateOverride,
+    max_code_size: Option<usize>,
+    db: &mut CacheDB<DB>,
+) -> EthResult<()>
 where
     DB: DatabaseRef,
     EthApiError: From<<DB as DatabaseRef>::Error>,
 {
     for (account, account_overrides) in overrides {
+        if let (Some(code), Some(max_code_size)) = (&account_overrides.code, max_code_size) {
+            if code.len() > max_code_size {
+                return Err(EthApiError::OverrideCodeTooLarge {
+                    account,
+                    size: code.len(),
+                    max_code_size,
+                })
+            }
+        }
         apply_account_override(account, account_overrides, db)?;
     }
     Ok(())
@@ -595,6 +664,10 @@ pub(crate) fn result_output(res: &ExecutionResult) -> Option<bytes::Bytes> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use jsonrpsee::types::error::{ErrorObject, INVALID_PARAMS_CODE};
+    use reth_provider::test_utils::NoopProvider;
+    use reth_revm::database::StateProviderDatabase;
+    use revm::primitives::AccountInfo;
 
     #[test]
     fn test_ensure_0_fallback() {
@@ -625,4 +698,162 @@ mod tests {
         assert_eq!(gas_price, U256::ZERO);
         assert_eq!(max_fee_per_blob_gas, Some(U256::from(99)));
     }
+
+    fn test_db() -> CacheDB<StateProviderDatabase<NoopProvider>> {
+        CacheDB::new(StateProviderDatabase::new(NoopProvider::default()))
+    }
+
+    /// Returns a database in which the caller has the given balance.
+    fn test_db_with_balance(
+        caller: Address,
+        balance: u64,
+    ) -> CacheDB<StateProviderDatabase<NoopProvider>> {
+        let mut db = test_db();
+        db.insert_account_info(
+            caller,
+            AccountInfo { balance: U256::from(balance), ..Default::default() },
+        );
+        db
+    }
+
+    #[test]
+    fn test_prepare_call_env_caps_gas() {
+        let limits = CallLimits::new(1_000_000).with_memory_limit(Some(1024));
+        let prepare = |gas: Option<u64>, limits: CallLimits| {
+            let request = CallRequest { gas: gas.map(U256::from), ..Default::default() };
+            prepare_call_env(
+                CfgEnv::default(),
+                BlockEnv::default(),
+                request,
+                limits,
+                &mut test_db(),
+                EvmOverrides::default(),
+            )
+            .unwrap()
+        };
+
+        // the gas of the request is clamped to the gas cap
+        let env = prepare(Some(50_000_000), limits);
+        assert_eq!(env.tx.gas_limit, 1_000_000);
+        assert_eq!(env.cfg.memory_limit, 1024);
+        assert_eq!(prepare(Some(21_000), limits).tx.gas_limit, 21_000);
+
+        // without gas in the request, the gas cap is used
+        assert_eq!(prepare(None, limits).tx.gas_limit, 1_000_000);
+
+        // trusted limits lift the memory limit, but keep the gas cap
+        let env = prepare(Some(50_000_000), CallLimits::trusted(1_000_000));
+        assert_eq!(env.tx.gas_limit, 1_000_000);
+        assert_eq!(env.cfg.memory_limit, u64::MAX);
+        assert_eq!(prepare(None, CallLimits::trusted(1_000_000)).tx.gas_limit, 1_000_000);
+    }
+
+    #[test]
+    fn test_prepare_call_env_caps_caller_allowance() {
+        let caller = Address::random();
+        let prepare = |balance: u64, limits: CallLimits| {
+            let request = CallRequest {
+                from: Some(caller),
+                gas_price: Some(U256::from(1)),
+                ..Default::default()
+            };
+            prepare_call_env(
+                CfgEnv::default(),
+                BlockEnv::default(),
+                request,
+                limits,
+                &mut test_db_with_balance(caller, balance),
+                EvmOverrides::default(),
+            )
+            .unwrap()
+        };
+
+        // the allowance is capped by the gas cap
+        let limits = CallLimits::new(1_000_000);
+        assert_eq!(prepare(u64::MAX, limits).tx.gas_limit, 1_000_000);
+        assert_eq!(prepare(u64::MAX, CallLimits::trusted(1_000_000)).tx.gas_limit, 1_000_000);
+
+        // a lower allowance is used as is
+        assert_eq!(prepare(50_000, limits).tx.gas_limit, 50_000);
+    }
+
+    #[test]
+    fn test_prepare_access_list_env_caps_gas() {
+        let limits = CallLimits::new(1_000_000).with_memory_limit(Some(1024));
+        let prepare = |gas: u64, limits: CallLimits| {
+            let request = CallRequest { gas: Some(U256::from(gas)), ..Default::default() };
+            prepare_access_list_env(
+                CfgEnv::default(),
+                BlockEnv::default(),
+                request,
+                limits,
+                &mut test_db(),
+            )
+            .unwrap()
+        };
+
+        let env = prepare(50_000_000, limits);
+        assert_eq!(env.tx.gas_limit, 1_000_000);
+        assert_eq!(env.cfg.memory_limit, 1024);
+        assert!(env.cfg.disable_block_gas_limit);
+        assert_eq!(prepare(21_000, limits).tx.gas_limit, 21_000);
+
+        let env = prepare(50_000_000, CallLimits::trusted(1_000_000));
+        assert_eq!(env.tx.gas_limit, 1_000_000);
+        assert_eq!(env.cfg.memory_limit, u64::MAX);
+    }
+
+    #[test]
+    fn test_prepare_access_list_env_caps_caller_allowance() {
+        let caller = Address::random();
+        let prepare = |balance: u64| {
+            let request = CallRequest {
+                from: Some(caller),
+                gas_price: Some(U256::from(1)),
+                ..Default::default()
+            };
+            prepare_access_list_env(
+                CfgEnv::default(),
+                BlockEnv::default(),
+                request,
+                CallLimits::new(1_000_000),
+                &mut test_db_with_balance(caller, balance),
+            )
+            .unwrap()
+        };
+
+        // the allowance is applied first and must not lift the gas cap
+        assert_eq!(prepare(u64::MAX).tx.gas_limit, 1_000_000);
+        assert_eq!(prepare(50_000).tx.gas_limit, 50_000);
+    }
+
+    #[test]
+    fn test_override_code_too_large() {
+        let account = Address::random();
+        let prepare = |max_code_size: Option<usize>| {
+            let overrides = StateOverride::from([(
+                account,
+                AccountOverride { code: Some(vec![0x60; 10].into()), ..Default::default() },
+            )]);
+            prepare_call_env(
+                CfgEnv::default(),
+                BlockEnv::default(),
+                CallRequest::default(),
+                CallLimits::default().with_max_code_size(max_code_size),
+                &mut test_db(),
+                EvmOverrides::state(Some(overrides)),
+            )
+        };
+
+        let err = prepare(Some(9)).unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::OverrideCodeTooLarge { account: a, size: 10, max_code_size: 9 } if a == account
+        ));
+        let err: ErrorObject<'static> = err.into();
+        assert_eq!(err.code(), INVALID_PARAMS_CODE);
+
+        prepare(Some(10)).unwrap();
+        prepare(None).unwrap();
+    }
 }
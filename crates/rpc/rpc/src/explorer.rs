@@ -0,0 +1,875 @@
+//! `explorer` namespace handler implementation.
+//!
+//! Lightweight block explorers need a few per address aggregates that the standard namespaces
+//! can't serve without scanning the entire chain, like the number of transactions of an address
+//! or the transaction that created a contract. The [ExplorerIndex] keeps these aggregates in
+//! memory and updates them incrementally with every new canonical block, so an explorer can run
+//! against a single node without an external indexer.
+//!
+//! The index covers the blocks that are backfilled on startup, see
+//! [ExplorerConfig::backfill_blocks], and all blocks that are committed afterwards. It is not
+//! persisted, so it starts over on every restart, and it is bounded by
+//! [ExplorerConfig::max_addresses].
+
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use jsonrpsee::core::RpcResult;
+use parking_lot::RwLock;
+use reth_interfaces::{provider::ProviderError, RethError, RethResult};
+use reth_primitives::{
+    hex_literal::hex, Address, Block, BlockNumber, Log, Receipt, TransactionKind,
+    TransactionSigned, H256, U256, U64,
+};
+use reth_provider::{BlockReader, CanonStateNotification, Chain};
+use reth_rpc_api::ExplorerApiServer;
+use reth_rpc_types::{AddressActivity, ContractCreation, HistoryRange, TokenTransfer};
+use revm_primitives::utilities::create_address;
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::Arc,
+};
+use tracing::{debug, warn};
+
+/// Topic of the ERC-20 and ERC-721 `Transfer(address,address,uint256)` event.
+pub const TRANSFER_EVENT_TOPIC: H256 =
+    H256(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"));
+
+/// Default max number of token transfers that are kept per address.
+pub const DEFAULT_EXPLORER_MAX_TRANSFERS_PER_ADDRESS: usize = 1000;
+
+/// Default max depth of a reorg that can be reverted without rebuilding the index.
+pub const DEFAULT_EXPLORER_MAX_REORG_DEPTH: u64 = 64;
+
+/// Default max number of addresses that are kept in the index.
+pub const DEFAULT_EXPLORER_MAX_ADDRESSES: u32 = 100_000;
+
+/// Settings for the [ExplorerIndex].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerConfig {
+    /// Max number of most recent token transfers that are kept per address.
+    ///
+    /// Default is 1000.
+    pub max_transfers_per_address: usize,
+    /// Number of blocks up to the tip that are indexed on startup.
+    ///
+    /// Default is 0.
+    pub backfill_blocks: u64,
+    /// Max depth of a reorg that can be reverted, the index is rebuilt on deeper reorgs.
+    ///
+    /// Default is 64.
+    pub max_reorg_depth: u64,
+    /// Max number of addresses that are kept in the index.
+    ///
+    /// Once exceeded, all aggregates of the least recently updated address are dropped.
+    ///
+    /// Default is 100000.
+    pub max_addresses: u32,
+}
+
+impl ExplorerConfig {
+    /// Configures the max number of most recent token transfers that are kept per address.
+    pub fn max_transfers_per_address(mut self, max_transfers_per_address: usize) -> Self {
+        self.max_transfers_per_address = max_transfers_per_address;
+        self
+    }
+
+    /// Configures the number of blocks up to the tip that are indexed on startup.
+    pub fn backfill_blocks(mut self, backfill_blocks: u64) -> Self {
+        self.backfill_blocks = backfill_blocks;
+        self
+    }
+
+    /// Configures the max depth of a reorg that can be reverted.
+    pub fn max_reorg_depth(mut self, max_reorg_depth: u64) -> Self {
+        self.max_reorg_depth = max_reorg_depth;
+        self
+    }
+
+    /// Configures the max number of addresses that are kept in the index.
+    pub fn max_addresses(mut self, max_addresses: u32) -> Self {
+        self.max_addresses = max_addresses;
+        self
+    }
+}
+
+impl Default for ExplorerConfig {
+    fn default() -> Self {
+        Self {
+            max_transfers_per_address: DEFAULT_EXPLORER_MAX_TRANSFERS_PER_ADDRESS,
+            backfill_blocks: 0,
+            max_reorg_depth: DEFAULT_EXPLORER_MAX_REORG_DEPTH,
+            max_addresses: DEFAULT_EXPLORER_MAX_ADDRESSES,
+        }
+    }
+}
+
+/// In-memory index of per address aggregates of the canonical chain.
+///
+/// Blocks are indexed in order. For every block an undo entry is kept, so reorgs up to
+/// [ExplorerConfig::max_reorg_depth] blocks deep can be reverted. On deeper reorgs the index is
+/// cleared and starts over at the new chain.
+///
+/// At most [ExplorerConfig::max_addresses] addresses are kept. Once exceeded, the least recently
+/// updated address is dropped, so its aggregates only cover the blocks indexed after that.
+#[derive(Debug, Clone)]
+pub struct ExplorerIndex {
+    inner: Arc<ExplorerIndexInner>,
+}
+
+impl ExplorerIndex {
+    /// Creates a new, empty index.
+    pub fn new(config: ExplorerConfig) -> Self {
+        let state = RwLock::new(IndexState::new(&config));
+        Self { inner: Arc::new(ExplorerIndexInner { config, state }) }
+    }
+
+    /// Returns the range of indexed blocks.
+    pub fn indexed_range(&self) -> Option<HistoryRange> {
+        let (from, to) = self.inner.state.read().range?;
+        HistoryRange::new(from, to)
+    }
+
+    /// Returns the activity of the address in the indexed blocks.
+    pub fn address_activity(&self, address: Address) -> Option<AddressActivity> {
+        self.inner.state.read().activity.get(&address).map(|activity| AddressActivity {
+            transaction_count: U64::from(activity.transaction_count),
+            first_seen: U64::from(activity.first_seen),
+            last_seen: U64::from(activity.last_seen),
+        })
+    }
+
+    /// Returns the creation info of the contract, if it was created in an indexed block.
+    pub fn contract_creation(&self, address: Address) -> Option<ContractCreation> {
+        self.inner.state.read().creations.get(&address).copied()
+    }
+
+    /// Returns up to `limit` of the most recent token transfers from or to the address, newest
+    /// first.
+    pub fn token_transfers(&self, address: Address, limit: usize) -> Vec<TokenTransfer> {
+        self.inner
+            .state
+            .read()
+            .transfers
+            .get(&address)
+            .map(|transfers| transfers.iter().rev().take(limit).copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Indexes the blocks of the chain, reverting all indexed blocks that the chain replaces.
+    ///
+    /// Blocks missing between the last indexed block and the chain are read from the database,
+    /// see [Self::fill_gap]. The chain is indexed even if that fails, since the index is cleared
+    /// then.
+    pub fn commit<Provider: BlockReader>(
+        &self,
+        provider: &Provider,
+        chain: &Chain,
+    ) -> RethResult<()> {
+        let first = chain.first().number;
+        self.revert_to(first.saturating_sub(1));
+        let filled = self.fill_gap(provider, first);
+
+        let mut state = self.inner.state.write();
+        for (number, block) in chain.blocks() {
+            state.index_block(
+                &self.inner.config,
+                *number,
+                &block.block.body,
+                &block.senders,
+                chain.state().receipts_by_block(*number),
+            );
+        }
+        filled
+    }
+
+    /// Reverts all indexed blocks above the given block.
+    ///
+    /// Clears the index if the blocks are no longer revertible.
+    pub fn revert_to(&self, block: BlockNumber) {
+        let mut state = self.inner.state.write();
+        let Some((from, to)) = state.range else { return };
+        if to <= block {
+            return
+        }
+        let revertible = state.journal.front().map_or(false, |undo| undo.number <= block + 1);
+        if !revertible {
+            if block >= from {
+                warn!(target: "rpc::explorer", block, "Reorg is too deep, clearing explorer index");
+            }
+            *state = IndexState::new(&self.inner.config);
+            return
+        }
+        while state.range.map_or(false, |(_, to)| to > block) {
+            let undo = state.journal.pop_back().expect("journal covers reverted blocks");
+            state.revert_block(&self.inner.config, undo);
+        }
+    }
+
+    /// Indexes the canonical blocks before `next` from the database.
+    ///
+    /// This indexes all blocks after the last indexed block. If no block is indexed yet, or there
+    /// are more than [ExplorerConfig::backfill_blocks] blocks after the last indexed block, the
+    /// index starts over with the last [ExplorerConfig::backfill_blocks] blocks instead.
+    ///
+    /// If a block can't be read, the index is cleared, so it never skips a block.
+    pub fn fill_gap<Provider: BlockReader>(
+        &self,
+        provider: &Provider,
+        next: BlockNumber,
+    ) -> RethResult<()> {
+        let window_start = next.saturating_sub(self.inner.config.backfill_blocks);
+        let start = {
+            let mut state = self.inner.state.write();
+            match state.range {
+                Some((_, to)) if to + 1 >= window_start => to + 1,
+                Some((_, to)) => {
+                    warn!(target: "rpc::explorer", last = to, next, "Too many blocks to index, clearing explorer index");
+                    *state = IndexState::new(&self.inner.config);
+                    window_start
+                }
+                None => window_start,
+            }
+        };
+        if start < next {
+            debug!(target: "rpc::explorer", start, end = next - 1, "Indexing blocks from database");
+        }
+
+        for number in start..next {
+            let (block, senders, receipts) = match read_block(provider, number) {
+                Ok(Some(read)) => read,
+                Ok(None) => {
+                    warn!(target: "rpc::explorer", number, "Block is missing, clearing explorer index");
+                    self.clear();
+                    return Ok(())
+                }
+                Err(err) => {
+                    self.clear();
+                    return Err(err)
+                }
+            };
+            self.inner.state.write().index_block(
+                &self.inner.config,
+                number,
+                &block.body,
+                &senders,
+                &receipts,
+            );
+        }
+        Ok(())
+    }
+
+    /// Drops all indexed blocks.
+    fn clear(&self) {
+        *self.inner.state.write() = IndexState::new(&self.inner.config);
+    }
+}
+
+/// Reads the block with its senders and receipts from the database.
+///
+/// Pruned senders are recovered from the signatures, pruned receipts are returned as missing.
+fn read_block<Provider: BlockReader>(
+    provider: &Provider,
+    number: BlockNumber,
+) -> RethResult<Option<(Block, Vec<Address>, Vec<Option<Receipt>>)>> {
+    let (block, senders) = match provider.block_with_senders(number) {
+        Ok(Some(block)) => (block.block, block.senders),
+        Ok(None) => return Ok(None),
+        Err(RethError::Provider(ProviderError::HistoryUnavailable { .. })) => {
+            let Some(block) = provider.block(number.into())? else { return Ok(None) };
+            let Some(senders) = block.senders() else { return Ok(None) };
+            (block, senders)
+        }
+        Err(err) => return Err(err),
+    };
+    let receipts = match provider.receipts_by_block(number.into()) {
+        Ok(receipts) => receipts.unwrap_or_default(),
+        Err(RethError::Provider(ProviderError::HistoryUnavailable { .. })) => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    Ok(Some((block, senders, receipts.into_iter().map(Some).collect())))
+}
+
+#[derive(Debug)]
+struct ExplorerIndexInner {
+    config: ExplorerConfig,
+    state: RwLock<IndexState>,
+}
+
+/// Activity of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Activity {
+    transaction_count: u64,
+    first_seen: BlockNumber,
+    last_seen: BlockNumber,
+}
+
+/// Changes made to the index by a block, used to revert it.
+#[derive(Debug, Default)]
+struct BlockUndo {
+    number: BlockNumber,
+    /// Activity of all touched addresses before the block was indexed.
+    activity: HashMap<Address, Option<Activity>>,
+    /// Contracts created in the block.
+    creations: Vec<Address>,
+    /// Addresses a transfer was appended to, once per appended transfer, with the oldest transfer
+    /// of the address that was dropped to make room for it.
+    transfers: Vec<(Address, Option<TokenTransfer>)>,
+}
+
+struct IndexState {
+    /// First and last indexed block.
+    range: Option<(BlockNumber, BlockNumber)>,
+    activity: HashMap<Address, Activity>,
+    creations: HashMap<Address, ContractCreation>,
+    transfers: HashMap<Address, VecDeque<TokenTransfer>>,
+    /// All addresses in the index, in the order of their last update.
+    addresses: LruMap<Address, (), ByLength>,
+    /// Undo entries of the most recent blocks, oldest first.
+    journal: VecDeque<BlockUndo>,
+}
+
+impl IndexState {
+    fn new(config: &ExplorerConfig) -> Self {
+        Self {
+            range: None,
+            activity: Default::default(),
+            creations: Default::default(),
+            transfers: Default::default(),
+            addresses: LruMap::new(ByLength::new(config.max_addresses.max(1))),
+            journal: Default::default(),
+        }
+    }
+
+    fn index_block(
+        &mut self,
+        config: &ExplorerConfig,
+        number: BlockNumber,
+        transactions: &[TransactionSigned],
+        senders: &[Address],
+        receipts: &[Option<Receipt>],
+    ) {
+        let mut undo = BlockUndo { number, ..Default::default() };
+        // the log index is unknown after a missing receipt, so later transfers of the block are
+        // not indexed
+        let mut log_index = Some(0u64);
+
+        for (idx, (tx, sender)) in transactions.iter().zip(senders).enumerate() {
+            // receipts may be missing, e.g. if they were pruned
+            let receipt = receipts.get(idx).and_then(Option::as_ref);
+            let mut touched = vec![*sender];
+            match tx.kind() {
+                TransactionKind::Call(to) if to != sender => touched.push(*to),
+                TransactionKind::Call(_) => {}
+                TransactionKind::Create => {
+                    if receipt.map_or(false, |receipt| receipt.success) {
+                        let contract = create_address(*sender, tx.nonce());
+                        let creation = ContractCreation {
+                            creator: *sender,
+                            transaction_hash: tx.hash(),
+                            block_number: U64::from(number),
+                        };
+                        self.track(config, contract);
+                        self.creations.insert(contract, creation);
+                        undo.creations.push(contract);
+                        touched.push(contract);
+                    }
+                }
+            }
+            for address in touched {
+                self.touch(config, address, number, &mut undo);
+            }
+
+            let Some(receipt) = receipt else {
+                log_index = None;
+                continue
+            };
+            let Some(first_log_index) = log_index else { continue };
+            for (log, log_index) in receipt.logs.iter().zip(first_log_index..) {
+                if let Some((from, to, value)) = decode_transfer(log) {
+                    let transfer = TokenTransfer {
+                        token: log.address,
+                        from,
+                        to,
+                        value,
+                        transaction_hash: tx.hash(),
+                        block_number: U64::from(number),
+                        log_index: U64::from(log_index),
+                    };
+                    self.push_transfer(config, from, transfer, &mut undo);
+                    if to != from {
+                        self.push_transfer(config, to, transfer, &mut undo);
+                    }
+                }
+            }
+            log_index = Some(first_log_index + receipt.logs.len() as u64);
+        }
+
+        self.range = Some((self.range.map_or(number, |(from, _)| from), number));
+        self.journal.push_back(undo);
+        while self.journal.len() as u64 > config.max_reorg_depth {
+            self.journal.pop_front();
+        }
+    }
+
+    /// Marks the address as most recently updated, dropping the least recently updated addresses
+    /// if the index is full.
+    fn track(&mut self, config: &ExplorerConfig, address: Address) {
+        if self.addresses.get(&address).is_some() {
+            return
+        }
+        while self.addresses.len() >= config.max_addresses.max(1) as usize {
+            let Some((oldest, _)) = self.addresses.pop_oldest() else { break };
+            self.activity.remove(&oldest);
+            self.creations.remove(&oldest);
+            self.transfers.remove(&oldest);
+        }
+        self.addresses.insert(address, ());
+    }
+
+    /// Records a transaction of the address in the given block.
+    fn touch(
+        &mut self,
+        config: &ExplorerConfig,
+        address: Address,
+        number: BlockNumber,
+        undo: &mut BlockUndo,
+    ) {
+        self.track(config, address);
+        let previous = self.activity.get(&address).copied();
+        undo.activity.entry(address).or_insert(previous);
+
+        let activity = self.activity.entry(address).or_insert(Activity {
+            transaction_count: 0,
+            first_seen: number,
+            last_seen: number,
+        });
+        activity.transaction_count += 1;
+        activity.last_seen = number;
+    }
+
+    fn push_transfer(
+        &mut self,
+        config: &ExplorerConfig,
+        address: Address,
+        transfer: TokenTransfer,
+        undo: &mut BlockUndo,
+    ) {
+        if config.max_transfers_per_address == 0 {
+            return
+        }
+        self.track(config, address);
+        let transfers = self.transfers.entry(address).or_default();
+        transfers.push_back(transfer);
+        let evicted = if transfers.len() > config.max_transfers_per_address {
+            transfers.pop_front()
+        } else {
+            None
+        };
+        undo.transfers.push((address, evicted));
+    }
+
+    /// Reverts the last indexed block.
+    ///
+    /// Aggregates of addresses that were dropped from the index since are not restored.
+    fn revert_block(&mut self, config: &ExplorerConfig, undo: BlockUndo) {
+        for (address, previous) in undo.activity {
+            match previous {
+                Some(activity) => {
+                    self.track(config, address);
+                    self.activity.insert(address, activity)
+                }
+                None => self.activity.remove(&address),
+            };
+        }
+        for contract in undo.creations {
+            self.creations.remove(&contract);
+        }
+        for (address, evicted) in undo.transfers.into_iter().rev() {
+            let Some(transfers) = self.transfers.get_mut(&address) else { continue };
+            transfers.pop_back();
+            if let Some(evicted) = evicted {
+                transfers.push_front(evicted);
+            }
+            if transfers.is_empty() {
+                self.transfers.remove(&address);
+            }
+        }
+
+        self.range = match self.range {
+            Some((from, _)) if from < undo.number => Some((from, undo.number - 1)),
+            _ => None,
+        };
+    }
+}
+
+impl fmt::Debug for IndexState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexState")
+            .field("range", &self.range)
+            .field("addresses", &self.addresses.len())
+            .field("journal", &self.journal.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decodes an ERC-20 or ERC-721 `Transfer` event into sender, receiver and amount or token id.
+fn decode_transfer(log: &Log) -> Option<(Address, Address, U256)> {
+    let topic_address = |topic: &H256| Address::from_slice(&topic.as_bytes()[12..]);
+    match log.topics.as_slice() {
+        // ERC-20, the amount is not indexed
+        [topic, from, to] if *topic == TRANSFER_EVENT_TOPIC && log.data.len() == 32 => {
+            Some((topic_address(from), topic_address(to), U256::try_from_be_slice(&log.data)?))
+        }
+        // ERC-721, the token id is indexed
+        [topic, from, to, id] if *topic == TRANSFER_EVENT_TOPIC => {
+            Some((topic_address(from), topic_address(to), U256::from_be_bytes(id.0)))
+        }
+        _ => None,
+    }
+}
+
+/// Awaits new chain events and applies them to the index.
+///
+/// Before that, the index is backfilled up to the current tip.
+pub async fn explorer_index_task<Provider, St>(
+    index: ExplorerIndex,
+    provider: Provider,
+    mut events: St,
+) where
+    Provider: BlockReader + 'static,
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+{
+    let backfill =
+        provider.best_block_number().and_then(|best| index.fill_gap(&provider, best + 1));
+    if let Err(err) = backfill {
+        warn!(target: "rpc::explorer", %err, "Failed to backfill explorer index");
+    }
+
+    while let Some(event) = events.next().await {
+        if let Some(reverted) = event.reverted() {
+            index.revert_to(reverted.first().number.saturating_sub(1));
+        }
+        if let Some(committed) = event.committed() {
+            if let Err(err) = index.commit(&provider, &committed) {
+                warn!(target: "rpc::explorer", %err, "Failed to index canonical blocks");
+            }
+        }
+    }
+}
+
+/// `explorer` API implementation.
+///
+/// This type provides the functionality for handling `explorer` related requests.
+#[derive(Debug, Clone)]
+pub struct ExplorerApi {
+    index: ExplorerIndex,
+}
+
+impl ExplorerApi {
+    /// Creates a new instance of `ExplorerApi` that serves the given index.
+    pub fn new(index: ExplorerIndex) -> Self {
+        Self { index }
+    }
+}
+
+#[async_trait]
+impl ExplorerApiServer for ExplorerApi {
+    /// Handler for `explorer_indexedRange`
+    async fn indexed_range(&self) -> RpcResult<Option<HistoryRange>> {
+        Ok(self.index.indexed_range())
+    }
+
+    /// Handler for `explorer_getAddressActivity`
+    async fn get_address_activity(&self, address: Address) -> RpcResult<Option<AddressActivity>> {
+        Ok(self.index.address_activity(address))
+    }
+
+    /// Handler for `explorer_getContractCreation`
+    async fn get_contract_creation(&self, address: Address) -> RpcResult<Option<ContractCreation>> {
+        Ok(self.index.contract_creation(address))
+    }
+
+    /// Handler for `explorer_getTokenTransfers`
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<TokenTransfer>> {
+        let limit = limit.unwrap_or(self.index.inner.config.max_transfers_per_address);
+        Ok(self.index.token_transfers(address, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Signature, Transaction, TxLegacy};
+    use reth_provider::test_utils::MockEthProvider;
+
+    const ALICE: Address = Address::repeat_byte(0xa);
+    const BOB: Address = Address::repeat_byte(0xb);
+    const TOKEN: Address = Address::repeat_byte(0xc);
+
+    fn tx(nonce: u64, to: TransactionKind) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, to, ..Default::default() }),
+            Signature::default(),
+        )
+    }
+
+    fn transfer_log(from: Address, to: Address, value: u64) -> Log {
+        Log {
+            address: TOKEN,
+            topics: vec![TRANSFER_EVENT_TOPIC, from.into(), to.into()],
+            data: H256::from_low_u64_be(value).as_bytes().to_vec().into(),
+        }
+    }
+
+    fn receipt(logs: Vec<Log>) -> Option<Receipt> {
+        Some(Receipt { success: true, logs, ..Default::default() })
+    }
+
+    fn index_block(
+        index: &ExplorerIndex,
+        number: BlockNumber,
+        txs: Vec<(Address, TransactionSigned, Option<Receipt>)>,
+    ) {
+        let (senders, txs): (Vec<_>, Vec<_>) =
+            txs.into_iter().map(|(sender, tx, receipt)| (sender, (tx, receipt))).unzip();
+        let (txs, receipts): (Vec<_>, Vec<_>) = txs.into_iter().unzip();
+        index.inner.state.write().index_block(
+            &index.inner.config,
+            number,
+            &txs,
+            &senders,
+            &receipts,
+        );
+    }
+
+    #[test]
+    fn decode_transfers() {
+        let erc20 = transfer_log(ALICE, BOB, 100);
+        assert_eq!(decode_transfer(&erc20), Some((ALICE, BOB, U256::from(100))));
+
+        let erc721 = Log {
+            address: TOKEN,
+            topics: vec![TRANSFER_EVENT_TOPIC, ALICE.into(), BOB.into(), H256::from_low_u64_be(7)],
+            data: Default::default(),
+        };
+        assert_eq!(decode_transfer(&erc721), Some((ALICE, BOB, U256::from(7))));
+
+        let other = Log { topics: vec![H256::zero(), ALICE.into(), BOB.into()], ..erc20 };
+        assert_eq!(decode_transfer(&other), None);
+    }
+
+    #[test]
+    fn index_and_revert_blocks() {
+        let index = ExplorerIndex::new(ExplorerConfig::default());
+        assert_eq!(index.indexed_range(), None);
+
+        let creation = tx(0, TransactionKind::Create);
+        let contract = create_address(ALICE, 0);
+        index_block(&index, 1, vec![(ALICE, creation.clone(), receipt(vec![]))]);
+        index_block(
+            &index,
+            2,
+            vec![(
+                ALICE,
+                tx(1, TransactionKind::Call(TOKEN)),
+                receipt(vec![transfer_log(ALICE, BOB, 100)]),
+            )],
+        );
+
+        assert_eq!(index.indexed_range(), HistoryRange::new(1, 2));
+        assert_eq!(
+            index.address_activity(ALICE),
+            Some(AddressActivity {
+                transaction_count: U64::from(2),
+                first_seen: U64::from(1),
+                last_seen: U64::from(2),
+            })
+        );
+        assert_eq!(index.address_activity(contract).unwrap().transaction_count, U64::from(1));
+        assert_eq!(index.address_activity(BOB), None);
+        assert_eq!(
+            index.contract_creation(contract),
+            Some(ContractCreation {
+                creator: ALICE,
+                transaction_hash: creation.hash(),
+                block_number: U64::from(1),
+            })
+        );
+        assert_eq!(index.token_transfers(BOB, 10).len(), 1);
+        assert_eq!(index.token_transfers(ALICE, 10), index.token_transfers(BOB, 10));
+
+        index.revert_to(1);
+        assert_eq!(index.indexed_range(), HistoryRange::new(1, 1));
+        assert_eq!(index.address_activity(ALICE).unwrap().last_seen, U64::from(1));
+        assert!(index.token_transfers(BOB, 10).is_empty());
+        assert!(index.contract_creation(contract).is_some());
+
+        index.revert_to(0);
+        assert_eq!(index.indexed_range(), None);
+        assert_eq!(index.address_activity(ALICE), None);
+        assert_eq!(index.contract_creation(contract), None);
+    }
+
+    #[test]
+    fn cap_transfers_and_reorg_depth() {
+        let config = ExplorerConfig::default().max_transfers_per_address(2).max_reorg_depth(2);
+        let index = ExplorerIndex::new(config);
+        for number in 1..=3 {
+            index_block(
+                &index,
+                number,
+                vec![(
+                    ALICE,
+                    tx(number, TransactionKind::Call(TOKEN)),
+                    receipt(vec![transfer_log(ALICE, BOB, number)]),
+                )],
+            );
+        }
+
+        let transfers = index.token_transfers(ALICE, 10);
+        assert_eq!(
+            transfers.iter().map(|transfer| transfer.value).collect::<Vec<_>>(),
+            vec![U256::from(3), U256::from(2)]
+        );
+        assert_eq!(index.token_transfers(ALICE, 1).len(), 1);
+
+        // block 1 is no longer revertible
+        index.revert_to(1);
+        assert_eq!(index.indexed_range(), HistoryRange::new(1, 1));
+        index_block(&index, 2, vec![]);
+        index_block(&index, 3, vec![]);
+        index.revert_to(0);
+        assert_eq!(index.indexed_range(), None);
+        assert!(index.token_transfers(ALICE, 10).is_empty());
+    }
+
+    #[test]
+    fn revert_restores_evicted_transfers() {
+        let config = ExplorerConfig::default().max_transfers_per_address(2);
+        let index = ExplorerIndex::new(config);
+        for number in 1..=3 {
+            index_block(
+                &index,
+                number,
+                vec![(
+                    ALICE,
+                    tx(number, TransactionKind::Call(TOKEN)),
+                    receipt(vec![transfer_log(ALICE, BOB, number)]),
+                )],
+            );
+        }
+        let values = |address| {
+            index
+                .token_transfers(address, 10)
+                .iter()
+                .map(|transfer| transfer.value)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(BOB), vec![U256::from(3), U256::from(2)]);
+
+        // the transfer of block 1 that was dropped for the transfer of block 3 is restored
+        index.revert_to(2);
+        assert_eq!(values(ALICE), vec![U256::from(2), U256::from(1)]);
+        assert_eq!(values(BOB), vec![U256::from(2), U256::from(1)]);
+    }
+
+    #[test]
+    fn missing_receipt_keeps_log_indices() {
+        let index = ExplorerIndex::new(ExplorerConfig::default());
+        index_block(
+            &index,
+            1,
+            vec![
+                (
+                    ALICE,
+                    tx(0, TransactionKind::Call(TOKEN)),
+                    receipt(vec![transfer_log(ALICE, BOB, 1), transfer_log(BOB, ALICE, 2)]),
+                ),
+                (BOB, tx(0, TransactionKind::Call(TOKEN)), None),
+                (
+                    ALICE,
+                    tx(1, TransactionKind::Call(TOKEN)),
+                    receipt(vec![transfer_log(ALICE, BOB, 3)]),
+                ),
+            ],
+        );
+
+        // transfers before the missing receipt are indexed at their log index
+        let transfers = index.token_transfers(BOB, 10);
+        assert_eq!(
+            transfers
+                .iter()
+                .map(|transfer| (transfer.value, transfer.log_index))
+                .collect::<Vec<_>>(),
+            vec![(U256::from(2), U64::from(1)), (U256::from(1), U64::from(0))]
+        );
+        // the log index of later transfers is unknown, but the transactions are still counted
+        assert_eq!(index.address_activity(ALICE).unwrap().transaction_count, U64::from(2));
+        assert_eq!(index.address_activity(BOB).unwrap().transaction_count, U64::from(1));
+
+        // transactions are counted if all receipts of the block are missing
+        index.inner.state.write().index_block(
+            &index.inner.config,
+            2,
+            &[tx(2, TransactionKind::Call(TOKEN))],
+            &[ALICE],
+            &[],
+        );
+        assert_eq!(index.address_activity(ALICE).unwrap().transaction_count, U64::from(3));
+    }
+
+    #[test]
+    fn cap_addresses() {
+        let config = ExplorerConfig::default().max_addresses(2);
+        let index = ExplorerIndex::new(config);
+        let carol = Address::repeat_byte(0xd);
+
+        index_block(&index, 1, vec![(ALICE, tx(0, TransactionKind::Call(ALICE)), None)]);
+        index_block(&index, 2, vec![(BOB, tx(0, TransactionKind::Call(BOB)), None)]);
+        assert!(index.address_activity(ALICE).is_some());
+
+        // the least recently updated address is dropped
+        index_block(&index, 3, vec![(carol, tx(0, TransactionKind::Call(carol)), None)]);
+        assert_eq!(index.address_activity(ALICE), None);
+        assert!(index.address_activity(BOB).is_some());
+        assert!(index.address_activity(carol).is_some());
+
+        // dropped addresses start over once they are active again
+        index_block(&index, 4, vec![(ALICE, tx(1, TransactionKind::Call(ALICE)), None)]);
+        assert_eq!(index.address_activity(ALICE).unwrap().first_seen, U64::from(4));
+        assert_eq!(index.address_activity(BOB), None);
+    }
+
+    #[test]
+    fn fill_gap_from_database() {
+        let provider = MockEthProvider::default();
+        for number in 1..=10 {
+            let header = Header { number, ..Default::default() };
+            provider
+                .add_block(H256::from_low_u64_be(number), Block { header, ..Default::default() });
+        }
+
+        let index = ExplorerIndex::new(ExplorerConfig::default().backfill_blocks(2));
+        index_block(&index, 1, vec![(ALICE, tx(0, TransactionKind::Call(BOB)), None)]);
+
+        // a gap larger than the backfill blocks starts over with the last backfill blocks
+        index.fill_gap(&provider, 10).unwrap();
+        assert_eq!(index.indexed_range(), HistoryRange::new(8, 9));
+        assert_eq!(index.address_activity(ALICE), None);
+
+        index.fill_gap(&provider, 11).unwrap();
+        assert_eq!(index.indexed_range(), HistoryRange::new(8, 10));
+
+        // a missing block clears the index instead of leaving a hole
+        index.fill_gap(&provider, 13).unwrap();
+        assert_eq!(index.indexed_range(), None);
+    }
+}
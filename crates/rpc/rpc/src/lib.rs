@@ -30,12 +30,14 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+pub mod explorer;
 mod layers;
 mod net;
 mod otterscan;
 mod reth;
 mod rpc;
 mod trace;
+pub mod trace_cache;
 pub mod tracing_call;
 mod txpool;
 mod web3;
@@ -44,12 +46,14 @@ pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
+pub use explorer::{ExplorerApi, ExplorerConfig, ExplorerIndex};
 pub use layers::{AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
+pub use trace_cache::{TraceCache, TraceCacheConfig};
 pub use tracing_call::{TracingCallGuard, TracingCallPool};
 pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
@@ -2,9 +2,12 @@ use crate::eth::error::{EthApiError, EthResult};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_interfaces::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_primitives::{Address, BlockId, BlockNumber, PrunePart, U256};
+use reth_provider::{
+    BlockReaderIdExt, ChangeSetReader, PruneCheckpointReader, StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{HistoryRange, HistoryRanges};
 use reth_tasks::TaskSpawner;
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
@@ -33,7 +36,8 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + StateProviderFactory + PruneCheckpointReader + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +85,54 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the ranges of blocks for which historical data is available locally.
+    pub async fn history_ranges(&self) -> EthResult<HistoryRanges> {
+        self.on_blocking_task(|this| async move { this.try_history_ranges() }).await
+    }
+
+    fn try_history_ranges(&self) -> EthResult<HistoryRanges> {
+        let best = self.provider().best_block_number()?;
+
+        // highest block for which the data of the given part was pruned
+        let pruned = |part| -> EthResult<Option<BlockNumber>> {
+            Ok(self
+                .provider()
+                .get_prune_checkpoint(part)?
+                .and_then(|checkpoint| checkpoint.block_number))
+        };
+        let available = |part| -> EthResult<_> {
+            Ok(HistoryRange::new(pruned(part)?.map_or(0, |block_number| block_number + 1), best))
+        };
+        // the state of a block is the state _after_ its changes are applied, so the state of the
+        // highest pruned block can still be served
+        let available_state = |part| -> EthResult<_> {
+            Ok(HistoryRange::new(pruned(part)?.unwrap_or_default(), best))
+        };
+
+        // headers and bodies have no prune part, they're stored from genesis on once the bodies
+        // were downloaded
+        let blocks = match self.provider().block_body_indices(0)? {
+            Some(_) => HistoryRange::new(0, best),
+            None => None,
+        };
+
+        Ok(HistoryRanges {
+            blocks,
+            senders: available(PrunePart::SenderRecovery)?,
+            transaction_lookup: available(PrunePart::TransactionLookup)?,
+            receipts: available(PrunePart::Receipts)?,
+            account_history: available_state(PrunePart::AccountHistory)?,
+            storage_history: available_state(PrunePart::StorageHistory)?,
+        })
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + StateProviderFactory + PruneCheckpointReader + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,6 +141,11 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(RethApi::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_historyRanges`
+    async fn reth_history_ranges(&self) -> RpcResult<HistoryRanges> {
+        Ok(RethApi::history_ranges(self).await?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -6,14 +6,16 @@ use crate::{
         EthTransactions,
     },
     result::internal_rpc_err,
+    trace_cache::{TraceCache, TraceCacheKey},
     TracingCallGuard,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{base_block_reward, block_reward};
-use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, SealedHeader, H256, U256};
+use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, SealedBlock, SealedHeader, H256, U256};
 use reth_provider::{
-    BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_revm::{
     database::{StateProviderDatabase, SubState},
@@ -28,7 +30,10 @@ use reth_rpc_types::{
     trace::{filter::TraceFilter, parity::*},
     BlockError, BlockOverrides, CallRequest, Index, TransactionInfo,
 };
-use revm::{db::CacheDB, primitives::Env};
+use revm::{
+    db::CacheDB,
+    primitives::{BlockEnv, CfgEnv, Env},
+};
 use revm_primitives::{db::DatabaseCommit, ExecutionResult, ResultAndState};
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
@@ -38,6 +43,8 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `trace` related requests.
 pub struct TraceApi<Provider, Eth> {
     inner: Arc<TraceApiInner<Provider, Eth>>,
+    /// Optional cache for the traces of recent blocks.
+    trace_cache: Option<TraceCache>,
 }
 
 // === impl TraceApi ===
@@ -51,7 +58,13 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     /// Create a new instance of the [TraceApi]
     pub fn new(provider: Provider, eth_api: Eth, tracing_call_guard: TracingCallGuard) -> Self {
         let inner = Arc::new(TraceApiInner { provider, eth_api, tracing_call_guard });
-        Self { inner }
+        Self { inner, trace_cache: None }
+    }
+
+    /// Configures the [TraceCache] for the traces of recent blocks.
+    pub fn with_trace_cache(mut self, trace_cache: Option<TraceCache>) -> Self {
+        self.trace_cache = trace_cache;
+        self
     }
 
     /// Acquires a permit to execute a tracing call.
@@ -139,7 +152,7 @@ where
         let at = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Pending));
         let (cfg, block_env, at) = self.inner.eth_api.evm_env_at(at).await?;
 
-        let gas_limit = self.inner.eth_api.call_gas_limit();
+        let limits = self.inner.eth_api.call_limits();
         // execute all transactions on top of each other and record the traces
         self.inner
             .eth_api
@@ -154,7 +167,7 @@ where
                         cfg.clone(),
                         block_env.clone(),
                         call,
-                        gas_limit,
+                        limits,
                         &mut db,
                         Default::default(),
                     )?;
@@ -309,6 +322,31 @@ where
             None => return Ok(None),
         };
 
+        self.trace_sealed_block_with(cfg, block_env, block, config, f).await.map(Some)
+    }
+
+    /// Like [Self::trace_block_with], but replays an already fetched block with the given evm
+    /// environment of that block.
+    async fn trace_sealed_block_with<F, R>(
+        &self,
+        cfg: CfgEnv,
+        block_env: BlockEnv,
+        block: SealedBlock,
+        config: TracingInspectorConfig,
+        f: F,
+    ) -> EthResult<Vec<R>>
+    where
+        F: for<'a> Fn(
+                TransactionInfo,
+                TracingInspector,
+                ExecutionResult,
+                &'a revm_primitives::State,
+                &'a CacheDB<StateProviderDatabase<StateProviderBox<'a>>>,
+            ) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
         // we need to get the state of the parent block because we're replaying this block on top of
         // its parent block's state
         let state_at = block.parent_hash;
@@ -355,67 +393,119 @@ where
                 Ok(results)
             })
             .await
-            .map(Some)
     }
 
     /// Returns traces created at given block.
+    ///
+    /// If a [TraceCache] is configured, the traces of recent blocks are served from the cache.
+    /// The pending block is never cached.
     pub async fn trace_block(
         &self,
         block_id: BlockId,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
-        let traces = self.trace_block_with(
-            block_id,
-            TracingInspectorConfig::default_parity(),
-            |tx_info, inspector, res, _, _| {
-                let traces = inspector
-                    .with_transaction_gas_used(res.gas_used())
-                    .into_parity_builder()
-                    .into_localized_transaction_traces(tx_info);
-                Ok(traces)
-            },
-        );
-
-        let block = self.inner.eth_api.block_by_id(block_id);
-        let (maybe_traces, maybe_block) = futures::try_join!(traces, block)?;
-
-        let mut maybe_traces =
-            maybe_traces.map(|traces| traces.into_iter().flatten().collect::<Vec<_>>());
-
-        if let (Some(block), Some(traces)) = (maybe_block, maybe_traces.as_mut()) {
-            if let Some(header_td) = self.provider().header_td(&block.header.hash)? {
-                if let Some(base_block_reward) = base_block_reward(
-                    self.provider().chain_spec().as_ref(),
-                    block.header.number,
-                    block.header.difficulty,
-                    header_td,
-                ) {
+        // the locally built pending block can't be looked up by its hash
+        let Some(cache) = self.trace_cache.as_ref().filter(|_| !block_id.is_pending()) else {
+            return self.replay_block_traces(block_id).await
+        };
+
+        let Some(block) = self.inner.eth_api.block_by_id(block_id).await? else { return Ok(None) };
+
+        let tip = self.provider().best_block_number()?;
+        let key = cache
+            .is_cacheable(block.number, tip)
+            .then(|| TraceCacheKey::new(block.hash, "trace_block", &()));
+        if let Some(key) = &key {
+            if let Some(traces) = cache.get(key).await {
+                return Ok(Some(traces))
+            }
+        }
+
+        // the fetched block is replayed, so the traces match the cache key
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash.into()).await?;
+        let traces = self.replay_sealed_block_traces(cfg, block_env, block).await?;
+        if let Some(key) = key {
+            cache.insert(key, traces.clone());
+        }
+        Ok(Some(traces))
+    }
+
+    /// Replays the given block and returns all of its traces, including the reward traces.
+    async fn replay_block_traces(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_id),
+            self.inner.eth_api.block_by_id(block_id),
+        )?;
+
+        let Some(block) = block else { return Ok(None) };
+        self.replay_sealed_block_traces(cfg, block_env, block).await.map(Some)
+    }
+
+    /// Replays the already fetched block with the given evm environment of that block and returns
+    /// all of its traces, including the reward traces.
+    async fn replay_sealed_block_traces(
+        &self,
+        cfg: CfgEnv,
+        block_env: BlockEnv,
+        block: SealedBlock,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        // the body is moved into the replay, only the header and the number of ommers are needed
+        // for the rewards
+        let header = block.header.clone();
+        let ommers = block.ommers.len();
+
+        let traces = self
+            .trace_sealed_block_with(
+                cfg,
+                block_env,
+                block,
+                TracingInspectorConfig::default_parity(),
+                |tx_info, inspector, res, _, _| {
+                    let traces = inspector
+                        .with_transaction_gas_used(res.gas_used())
+                        .into_parity_builder()
+                        .into_localized_transaction_traces(tx_info);
+                    Ok(traces)
+                },
+            )
+            .await?;
+
+        let mut traces = traces.into_iter().flatten().collect::<Vec<_>>();
+
+        if let Some(header_td) = self.provider().header_td(&header.hash)? {
+            if let Some(base_block_reward) = base_block_reward(
+                self.provider().chain_spec().as_ref(),
+                header.number,
+                header.difficulty,
+                header_td,
+            ) {
+                traces.push(reward_trace(
+                    &header,
+                    RewardAction {
+                        author: header.beneficiary,
+                        reward_type: RewardType::Block,
+                        value: U256::from(base_block_reward),
+                    },
+                ));
+
+                if ommers > 0 {
                     traces.push(reward_trace(
-                        &block.header,
+                        &header,
                         RewardAction {
-                            author: block.header.beneficiary,
-                            reward_type: RewardType::Block,
-                            value: U256::from(base_block_reward),
+                            author: header.beneficiary,
+                            reward_type: RewardType::Uncle,
+                            value: U256::from(
+                                block_reward(base_block_reward, ommers) - base_block_reward,
+                            ),
                         },
                     ));
-
-                    if !block.ommers.is_empty() {
-                        traces.push(reward_trace(
-                            &block.header,
-                            RewardAction {
-                                author: block.header.beneficiary,
-                                reward_type: RewardType::Uncle,
-                                value: U256::from(
-                                    block_reward(base_block_reward, block.ommers.len()) -
-                                        base_block_reward,
-                                ),
-                            },
-                        ));
-                    }
                 }
             }
         }
 
-        Ok(maybe_traces)
+        Ok(traces)
     }
 
     /// Replays all transactions in a block
@@ -564,7 +654,7 @@ impl<Provider, Eth> std::fmt::Debug for TraceApi<Provider, Eth> {
 }
 impl<Provider, Eth> Clone for TraceApi<Provider, Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), trace_cache: self.trace_cache.clone() }
     }
 }
 
@@ -0,0 +1,585 @@
+//! Disk-backed cache for the traces of entire blocks.
+//!
+//! Indexers commonly request the traces of the same recent blocks over and over again, for example
+//! via `debug_traceBlockByNumber` or `trace_block`. Replaying the block for every request is
+//! expensive, so the results are stored on disk, keyed by the hash of the block and the tracer
+//! configuration.
+
+use parking_lot::Mutex;
+use reth_primitives::{fs, keccak256, H256};
+use reth_tasks::TaskSpawner;
+use schnellru::{ByLength, LruMap};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+/// Default max number of cached block traces.
+pub const DEFAULT_TRACE_CACHE_MAX_ENTRIES: u32 = 1024;
+
+/// Default max number of blocks a block can be behind the tip for its traces to be cached.
+pub const DEFAULT_TRACE_CACHE_MAX_BLOCK_AGE: u64 = 256;
+
+/// Default max total size in bytes of all cached block traces.
+pub const DEFAULT_TRACE_CACHE_MAX_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Counter that makes the names of temporary files unique, so concurrent writes of the same traces
+/// don't interfere.
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Settings for the [TraceCache].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCacheConfig {
+    /// Directory the traces are stored in.
+    pub dir: PathBuf,
+    /// Max number of cached block traces.
+    ///
+    /// Default is 1024.
+    pub max_entries: u32,
+    /// Max number of blocks a block can be behind the tip for its traces to be cached.
+    ///
+    /// Default is 256.
+    pub max_block_age: u64,
+    /// Max total size in bytes of all cached block traces.
+    ///
+    /// Traces that are larger than this on their own are not cached.
+    ///
+    /// Default is 4 GiB.
+    pub max_bytes: u64,
+}
+
+impl TraceCacheConfig {
+    /// Creates a new config with default limits that stores the traces in the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_entries: DEFAULT_TRACE_CACHE_MAX_ENTRIES,
+            max_block_age: DEFAULT_TRACE_CACHE_MAX_BLOCK_AGE,
+            max_bytes: DEFAULT_TRACE_CACHE_MAX_BYTES,
+        }
+    }
+
+    /// Configures the max number of cached block traces.
+    pub fn max_entries(mut self, max_entries: u32) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Configures the max number of blocks a block can be behind the tip to be cached.
+    pub fn max_block_age(mut self, max_block_age: u64) -> Self {
+        self.max_block_age = max_block_age;
+        self
+    }
+
+    /// Configures the max total size in bytes of all cached block traces.
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// Identifies the traces of a block that were created with a specific tracer configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceCacheKey {
+    block_hash: H256,
+    config_hash: H256,
+}
+
+impl TraceCacheKey {
+    /// Creates the key for the traces of the block created by the given method with the given
+    /// tracer configuration.
+    pub fn new(block_hash: H256, method: &str, config: &impl Serialize) -> Self {
+        let mut buf = method.as_bytes().to_vec();
+        buf.extend(serde_json::to_vec(config).expect("tracer config is serializable"));
+        Self { block_hash, config_hash: keccak256(buf) }
+    }
+
+    /// Returns the hash of the traced block.
+    pub fn block_hash(&self) -> H256 {
+        self.block_hash
+    }
+
+    /// Returns the name of the file the traces are stored in.
+    fn file_name(&self) -> String {
+        format!("{:x}-{:x}.json", self.block_hash, self.config_hash)
+    }
+
+    /// Parses the key from the name of a file created by [TraceCacheKey::file_name].
+    fn from_file_name(name: &str) -> Option<Self> {
+        let (block_hash, config_hash) = name.strip_suffix(".json")?.split_once('-')?;
+        Some(Self { block_hash: block_hash.parse().ok()?, config_hash: config_hash.parse().ok()? })
+    }
+}
+
+/// A bounded, disk-backed cache for the traces of entire blocks.
+///
+/// The traces of a block only depend on the block itself and the state of its parent, so they are
+/// keyed by block hash and remain valid across reorgs. Only blocks close to the tip are cached
+/// (see [TraceCache::is_cacheable]) and the least recently used traces are evicted once the cache
+/// exceeds either the configured number of entries or the configured total size.
+///
+/// All disk access is performed on blocking tasks.
+#[derive(Clone)]
+pub struct TraceCache {
+    inner: Arc<TraceCacheInner>,
+}
+
+impl TraceCache {
+    /// Opens the cache in the configured directory.
+    ///
+    /// This creates the directory if it does not exist yet and restores the entries stored by a
+    /// previous instance.
+    pub fn open(config: TraceCacheConfig, task_spawner: Box<dyn TaskSpawner>) -> io::Result<Self> {
+        fs::create_dir_all(&config.dir).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+        let mut existing = Vec::new();
+        for entry in std::fs::read_dir(&config.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else { continue };
+            match TraceCacheKey::from_file_name(name) {
+                Some(key) => {
+                    let Ok(metadata) = entry.metadata() else { continue };
+                    existing.push((metadata.modified().ok(), key, metadata.len()));
+                }
+                None if name.ends_with(".tmp") => {
+                    // leftover of an interrupted write
+                    let _ = fs::remove_file(entry.path());
+                }
+                None => {}
+            }
+        }
+        // restore in the order the traces were written, so the newest are evicted last
+        existing.sort_by_key(|(modified, _, _)| *modified);
+
+        let max_entries = config.max_entries.max(1);
+        let inner = TraceCacheInner {
+            entries: Mutex::new(Entries {
+                index: LruMap::new(ByLength::new(max_entries)),
+                total_bytes: 0,
+            }),
+            config,
+            task_spawner,
+        };
+        for (_, key, size) in existing {
+            if size > inner.config.max_bytes {
+                inner.remove_files(vec![key]);
+                continue;
+            }
+            inner.remove_files(inner.insert_entry(key, size));
+        }
+
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns true if the traces of the block with the given number should be cached, given the
+    /// current tip of the chain.
+    pub fn is_cacheable(&self, block_number: u64, tip: u64) -> bool {
+        tip.saturating_sub(block_number) <= self.inner.config.max_block_age
+    }
+
+    /// Returns true if the traces for the given key are cached.
+    pub fn contains(&self, key: &TraceCacheKey) -> bool {
+        self.inner.entries.lock().index.peek(key).is_some()
+    }
+
+    /// Returns the number of cached block traces.
+    pub fn len(&self) -> usize {
+        self.inner.entries.lock().index.len()
+    }
+
+    /// Returns the total size in bytes of all cached block traces.
+    pub fn total_bytes(&self) -> u64 {
+        self.inner.entries.lock().total_bytes
+    }
+
+    /// Returns true if no traces are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached traces for the given key, if any.
+    pub async fn get<T>(&self, key: &TraceCacheKey) -> Option<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        if self.inner.entries.lock().index.get(key).is_none() {
+            return None
+        }
+
+        let path = self.inner.path(key);
+        let (tx, rx) = oneshot::channel();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(read_entry(&path));
+        }));
+
+        match rx.await {
+            Ok(Ok(traces)) => Some(traces),
+            Ok(Err(err)) => {
+                debug!(target: "rpc::trace_cache", ?key, %err, "Failed to read cached traces");
+                self.inner.entries.lock().remove(key);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Stores the traces for the given key.
+    ///
+    /// The traces are serialized and written on a blocking task and become available once the
+    /// write completed. Traces that exceed the configured max total size on their own are not
+    /// stored.
+    ///
+    /// Traces are only stored if they can be restored without loss: the serialized traces are
+    /// deserialized and serialized again, and must produce the exact same JSON. Otherwise a cache
+    /// hit could return a different response than replaying the block.
+    pub fn insert<T>(&self, key: TraceCacheKey, traces: T)
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let data = match serialize_lossless(&traces) {
+                Ok(data) => data,
+                Err(err) => {
+                    debug!(target: "rpc::trace_cache", ?key, %err, "Failed to serialize traces");
+                    return
+                }
+            };
+            drop(traces);
+
+            let size = data.len() as u64;
+            if size > inner.config.max_bytes {
+                debug!(target: "rpc::trace_cache", ?key, size, "Traces exceed the max cache size");
+                return
+            }
+
+            if let Err(err) = write_entry(&inner.path(&key), &data) {
+                debug!(target: "rpc::trace_cache", ?key, %err, "Failed to write traces");
+                return
+            }
+            inner.remove_files(inner.insert_entry(key, size));
+        }));
+    }
+}
+
+impl std::fmt::Debug for TraceCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TraceCache")
+            .field("config", &self.inner.config)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Container type for [TraceCache]
+struct TraceCacheInner {
+    /// Settings of the cache.
+    config: TraceCacheConfig,
+    /// Index of all traces stored on disk.
+    entries: Mutex<Entries>,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl TraceCacheInner {
+    /// Returns the path of the file for the given key.
+    fn path(&self, key: &TraceCacheKey) -> PathBuf {
+        self.config.dir.join(key.file_name())
+    }
+
+    /// Adds the key with the size of its file to the index and returns the keys that had to be
+    /// evicted to make room for it.
+    fn insert_entry(&self, key: TraceCacheKey, size: u64) -> Vec<TraceCacheKey> {
+        let mut entries = self.entries.lock();
+        // the entry is replaced if it already exists
+        entries.remove(&key);
+
+        let mut evicted = Vec::new();
+        while entries.index.len() >= self.config.max_entries.max(1) as usize ||
+            entries.total_bytes + size > self.config.max_bytes
+        {
+            let Some((oldest, oldest_size)) = entries.index.pop_oldest() else { break };
+            entries.total_bytes -= oldest_size;
+            evicted.push(oldest);
+        }
+        entries.index.insert(key, size);
+        entries.total_bytes += size;
+        evicted
+    }
+
+    /// Removes the files of the given keys.
+    fn remove_files(&self, keys: Vec<TraceCacheKey>) {
+        for key in keys {
+            let _ = fs::remove_file(self.path(&key));
+        }
+    }
+}
+
+/// Index of the cached traces.
+struct Entries {
+    /// Sizes of the files of all traces, in the order of their last access.
+    index: LruMap<TraceCacheKey, u64, ByLength>,
+    /// Sum of all sizes in the index.
+    total_bytes: u64,
+}
+
+impl Entries {
+    /// Removes the key from the index.
+    fn remove(&mut self, key: &TraceCacheKey) {
+        if let Some(size) = self.index.remove(key) {
+            self.total_bytes -= size;
+        }
+    }
+}
+
+/// Serializes the traces and checks that they can be deserialized again without loss.
+fn serialize_lossless<T>(traces: &T) -> io::Result<Vec<u8>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let data = serde_json::to_vec(traces)?;
+    let restored: T = serde_json::from_slice(&data)?;
+    if serde_json::to_vec(&restored)? != data {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "traces do not roundtrip"))
+    }
+    Ok(data)
+}
+
+/// Reads and deserializes the traces stored in the file.
+fn read_entry<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+/// Writes the serialized traces to the file.
+///
+/// The data is written to a temporary file with a unique name first, so readers never observe
+/// partial writes, even if the same traces are written concurrently.
+fn write_entry(path: &Path, data: &[u8]) -> io::Result<()> {
+    let counter = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp = path.with_extension(format!("{}.{counter}.tmp", std::process::id()));
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+    writer.write_all(data)?;
+    writer.flush()?;
+    drop(writer);
+    fs::rename(tmp, path).map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, H160, U256, U64};
+    use reth_rpc_types::trace::{
+        geth::{CallFrame, DefaultFrame, GethTrace, PreStateFrame, TraceResult},
+        parity::{
+            Action, CallAction, CallOutput, CallType, LocalizedTransactionTrace, TraceOutput,
+            TransactionTrace,
+        },
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use std::time::Duration;
+
+    // See <https://github.com/ethereum/go-ethereum/tree/master/eth/tracers/internal/tracetest/testdata>
+    const STRUCT_LOGS: &str = include_str!("../../rpc-types/test_data/default/structlogs_01.json");
+    const CALL_TRACER: &str = include_str!("../../rpc-types/test_data/call_tracer/with_log.json");
+    const PRE_STATE_DIFF: &str =
+        include_str!("../../rpc-types/test_data/pre_state_tracer/diff_mode.json");
+
+    async fn wait_for(cache: &TraceCache, key: &TraceCacheKey) {
+        for _ in 0..100 {
+            if cache.contains(key) {
+                return
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("traces were not cached");
+    }
+
+    fn open(dir: &Path, max_entries: u32) -> TraceCache {
+        let config = TraceCacheConfig::new(dir).max_entries(max_entries);
+        TraceCache::open(config, Box::<TokioTaskExecutor>::default()).unwrap()
+    }
+
+    /// Stores the traces and asserts that the cached traces serialize to the same JSON.
+    async fn assert_roundtrip<T>(cache: &TraceCache, traces: T)
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + 'static,
+    {
+        let key = TraceCacheKey::new(H256::random(), "trace_block", &());
+        cache.insert(key, traces.clone());
+        wait_for(cache, &key).await;
+        let cached: T = cache.get(&key).await.unwrap();
+        assert_eq!(serde_json::to_vec(&cached).unwrap(), serde_json::to_vec(&traces).unwrap());
+    }
+
+    #[test]
+    fn key_file_name_roundtrip() {
+        let key = TraceCacheKey::new(H256::random(), "trace_block", &());
+        assert_eq!(TraceCacheKey::from_file_name(&key.file_name()), Some(key));
+        assert_ne!(key, TraceCacheKey::new(key.block_hash(), "debug_traceBlock", &()));
+        assert_eq!(TraceCacheKey::from_file_name("foo.tmp"), None);
+    }
+
+    #[test]
+    fn cacheable_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TraceCacheConfig::new(dir.path()).max_block_age(10);
+        let cache = TraceCache::open(config, Box::<TokioTaskExecutor>::default()).unwrap();
+        assert!(cache.is_cacheable(100, 100));
+        assert!(cache.is_cacheable(90, 100));
+        assert!(!cache.is_cacheable(89, 100));
+        assert!(cache.is_cacheable(101, 100));
+    }
+
+    #[tokio::test]
+    async fn insert_get_and_evict() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(dir.path(), 2);
+
+        let keys: Vec<_> =
+            (0..3).map(|_| TraceCacheKey::new(H256::random(), "trace_block", &())).collect();
+        assert_eq!(cache.get::<Vec<u64>>(&keys[0]).await, None);
+
+        for (idx, key) in keys.iter().enumerate() {
+            cache.insert(*key, vec![idx as u64]);
+            wait_for(&cache, key).await;
+        }
+
+        // the oldest entry was evicted
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get::<Vec<u64>>(&keys[0]).await, None);
+        assert!(!dir.path().join(keys[0].file_name()).exists());
+        assert_eq!(cache.get::<Vec<u64>>(&keys[1]).await, Some(vec![1]));
+        assert_eq!(cache.get::<Vec<u64>>(&keys[2]).await, Some(vec![2]));
+
+        // entries are restored from disk
+        drop(cache);
+        let cache = open(dir.path(), 2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get::<Vec<u64>>(&keys[2]).await, Some(vec![2]));
+    }
+
+    #[tokio::test]
+    async fn evict_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        // `[0]` is serialized to 3 bytes
+        let config = TraceCacheConfig::new(dir.path()).max_bytes(7);
+        let cache = TraceCache::open(config, Box::<TokioTaskExecutor>::default()).unwrap();
+
+        let keys: Vec<_> =
+            (0..3).map(|_| TraceCacheKey::new(H256::random(), "trace_block", &())).collect();
+        for (idx, key) in keys.iter().enumerate() {
+            cache.insert(*key, vec![idx as u64]);
+            wait_for(&cache, key).await;
+        }
+
+        // the oldest entry was evicted
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.total_bytes(), 6);
+        assert!(!cache.contains(&keys[0]));
+        assert!(!dir.path().join(keys[0].file_name()).exists());
+
+        // traces that exceed the max size on their own are not cached
+        let key = TraceCacheKey::new(H256::random(), "trace_block", &());
+        cache.insert(key, vec![1u64, 2, 3]);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!cache.contains(&key));
+        assert_eq!(cache.len(), 2);
+
+        // the budget is applied when entries are restored from disk
+        drop(cache);
+        let config = TraceCacheConfig::new(dir.path()).max_bytes(3);
+        let cache = TraceCache::open(config, Box::<TokioTaskExecutor>::default()).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.total_bytes(), 3);
+    }
+
+    #[test]
+    fn concurrent_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("traces.json");
+        let handles: Vec<_> = (0..8u8)
+            .map(|idx| {
+                let path = path.clone();
+                std::thread::spawn(move || write_entry(&path, &[b'0' + idx; 1024]).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // the file holds exactly one of the writes and no temporary files are left
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(data.len(), 1024);
+        assert!(data.iter().all(|byte| *byte == data[0]));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn lossless_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(dir.path(), 16);
+
+        let default: DefaultFrame = serde_json::from_str(STRUCT_LOGS).unwrap();
+        let call: CallFrame = serde_json::from_str(CALL_TRACER).unwrap();
+        let pre_state: PreStateFrame = serde_json::from_str(PRE_STATE_DIFF).unwrap();
+        assert!(matches!(pre_state, PreStateFrame::Diff(_)));
+        let geth_traces: Vec<TraceResult<GethTrace, String>> = vec![
+            TraceResult::Success { result: default.into() },
+            TraceResult::Success { result: call.into() },
+            TraceResult::Success { result: pre_state.into() },
+            TraceResult::Error { error: "execution timeout".to_string() },
+        ];
+        assert_roundtrip(&cache, geth_traces).await;
+
+        let parity_trace = LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action: Action::Call(CallAction {
+                    from: H160::random(),
+                    call_type: CallType::Call,
+                    gas: U64::from(21_000),
+                    input: Bytes::from(vec![1, 2, 3]),
+                    to: H160::random(),
+                    value: U256::from(1),
+                }),
+                error: None,
+                result: Some(TraceOutput::Call(CallOutput {
+                    gas_used: U64::from(21_000),
+                    output: Bytes::default(),
+                })),
+                subtraces: 0,
+                trace_address: vec![],
+            },
+            block_hash: Some(H256::random()),
+            block_number: Some(1),
+            transaction_hash: Some(H256::random()),
+            transaction_position: Some(0),
+        };
+        assert_roundtrip(&cache, vec![parity_trace]).await;
+    }
+
+    #[tokio::test]
+    async fn skip_lossy_traces() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = open(dir.path(), 16);
+
+        // a custom tracer result that looks like a prestate frame is restored as one
+        let result = serde_json::json!({ format!("{:?}", H160::random()): { "balance": 1 } });
+        let lossy: Vec<TraceResult<GethTrace, String>> =
+            vec![TraceResult::Success { result: GethTrace::JS(result) }];
+        let key = TraceCacheKey::new(H256::random(), "debug_traceBlock", &());
+        cache.insert(key, lossy);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!cache.contains(&key));
+    }
+}
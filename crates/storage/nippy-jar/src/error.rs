@@ -31,4 +31,22 @@ pub enum NippyJarError {
     PHFMissing,
     #[error("NippyJar was built without an index.")]
     UnsupportedFilterQuery,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    FsPathError(#[from] reth_primitives::fs::FsPathError),
+    #[error("File listed in manifest is missing: {0}")]
+    ManifestMissingFile(std::path::PathBuf),
+    #[error("File does not match its manifest entry: {0}")]
+    ManifestMismatch(std::path::PathBuf),
+    #[error("File is outside of the manifest directory: {0}")]
+    ManifestOutsideDirectory(std::path::PathBuf),
+    #[error("Manifest entry is not a plain relative path: {0}")]
+    ManifestInvalidPath(String),
+    #[error("Manifest is not signed.")]
+    ManifestUnsigned,
+    #[error("Manifest was signed by an untrusted key.")]
+    ManifestUntrustedSigner,
+    #[error("Manifest signature is invalid.")]
+    ManifestInvalidSignature,
 }
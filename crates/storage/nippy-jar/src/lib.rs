@@ -10,6 +10,7 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+use reth_primitives::fs::{self, Completion};
 use serde::{Deserialize, Serialize};
 use std::{
     clone::Clone,
@@ -40,6 +41,9 @@ pub use error::NippyJarError;
 mod cursor;
 pub use cursor::NippyJarCursor;
 
+pub mod manifest;
+pub use manifest::Manifest;
+
 const NIPPY_JAR_VERSION: usize = 1;
 
 /// A [`Row`] is a list of its selected column values.
@@ -181,6 +185,31 @@ where
         Ok(obj)
     }
 
+    /// Removes the data and index files of the jar, e.g. after its rows were pruned or compacted
+    /// into another jar.
+    ///
+    /// On Windows, a file that is still in use, e.g. by a cursor of another process, is removed on
+    /// the next reboot instead, in which case [`Completion::OnReboot`] is returned.
+    ///
+    /// If the directory of the jar has a [`Manifest`], the entries of both files are removed from
+    /// it.
+    pub fn delete(self) -> Result<Completion, NippyJarError> {
+        let mut completion = Completion::Done;
+        for path in [self.index_path(), self.data_path()] {
+            if fs::remove_file(path)? == Completion::OnReboot {
+                completion = Completion::OnReboot;
+            }
+        }
+        let dir = self.dir();
+        Manifest::update(&dir, |manifest| manifest.remove_jar(&dir, &self))?;
+        Ok(completion)
+    }
+
+    /// Returns the directory of the data and index files.
+    fn dir(&self) -> PathBuf {
+        self.data_path().parent().expect("exists").to_path_buf()
+    }
+
     /// Returns the path from the data file
     pub fn data_path(&self) -> PathBuf {
         self.path.clone().expect("exists")
@@ -240,6 +269,9 @@ where
     }
 
     /// Writes all data and configuration to a file and the offset index to another.
+    ///
+    /// If the directory of the jar has a [`Manifest`], the entries of both files are inserted or
+    /// replaced, keeping the block range of a replaced entry.
     pub fn freeze(
         &mut self,
         columns: Vec<impl IntoIterator<Item = Vec<u8>>>,
@@ -316,6 +348,14 @@ where
         // Write offsets and offset index to file
         self.freeze_offsets(offsets)?;
 
+        let dir = self.dir();
+        Manifest::update(&dir, |manifest| {
+            let block_range = manifest::relative_path(&dir, &self.data_path())
+                .ok()
+                .and_then(|path| manifest.entry(&path)?.block_range.clone());
+            manifest.insert_jar(&dir, self, block_range)
+        })?;
+
         Ok(())
     }
 
@@ -818,4 +858,64 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_delete_jar() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("jar");
+
+        let mut nippy = NippyJar::new_without_header(2, &data_path);
+        nippy.freeze(vec![col1, col2], num_rows).unwrap();
+        assert!(nippy.data_path().exists());
+        assert!(nippy.index_path().exists());
+
+        let nippy = NippyJar::load_without_header(&data_path).unwrap();
+        let index_path = nippy.index_path();
+        assert_eq!(nippy.delete().unwrap(), Completion::Done);
+        assert!(!data_path.exists());
+        assert!(!index_path.exists());
+    }
+
+    #[test]
+    fn test_manifest_in_sync() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("jar");
+
+        // without a manifest, none is created
+        let mut nippy = NippyJar::new_without_header(2, &data_path);
+        nippy.freeze(vec![col1.clone(), col2.clone()], num_rows).unwrap();
+        assert!(Manifest::load(dir.path()).is_err());
+
+        let manifest = Manifest::generate(dir.path(), |_| Some(0..=99)).unwrap();
+        manifest.save(dir.path()).unwrap();
+
+        // freezing again replaces the entries and keeps their block range
+        let mut nippy = NippyJar::new_without_header(2, &data_path).with_zstd(false, 5000);
+        nippy.freeze(vec![col1.clone(), col2.clone()], num_rows).unwrap();
+        let updated = Manifest::load(dir.path()).unwrap();
+        assert_ne!(updated, manifest);
+        assert_eq!(updated.entry("jar").unwrap().block_range, Some(0..=99));
+        updated.verify(dir.path()).unwrap();
+
+        // a new jar is added
+        let other_path = dir.path().join("other");
+        let mut other = NippyJar::new_without_header(2, &other_path);
+        other.freeze(vec![col1, col2], num_rows).unwrap();
+        let updated = Manifest::load(dir.path()).unwrap();
+        assert_eq!(updated.entries.len(), 4);
+        assert_eq!(updated.entry("other").unwrap().block_range, None);
+        updated.verify(dir.path()).unwrap();
+
+        // deleting the jar removes its entries
+        NippyJar::load_without_header(&data_path).unwrap().delete().unwrap();
+        let updated = Manifest::load(dir.path()).unwrap();
+        assert!(updated.entry("jar").is_none());
+        assert!(updated.entry("jar.idx").is_none());
+        assert_eq!(updated.entries.len(), 2);
+        updated.verify(dir.path()).unwrap();
+    }
 }
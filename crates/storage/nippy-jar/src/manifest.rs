@@ -0,0 +1,458 @@
+//! Manifest of all files in a directory of jars.
+//!
+//! The manifest lists every file with its size, SHA-256 hash and the block range it covers, so a
+//! copy of the directory, e.g. a distributed snapshot or a mirrored datadir, can be verified
+//! file-by-file. The manifest itself can be signed with a secp256k1 key, which authenticates the
+//! whole directory once the signer's public key is trusted.
+
+use crate::{NippyJar, NippyJarError};
+use reth_primitives::{fs, hex};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SecretKey, SECP256K1};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// Name of the manifest file inside the directory it describes.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Name of the file the manifest is written to before it replaces [`MANIFEST_FILE_NAME`].
+const MANIFEST_TMP_FILE_NAME: &str = "manifest.json.tmp";
+
+/// Current version of the manifest format.
+const MANIFEST_VERSION: u32 = 1;
+
+/// A single file listed in the [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Path of the file relative to the manifest directory, with `/` as separator.
+    pub path: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Hex encoded SHA-256 hash of the file content.
+    pub sha256: String,
+    /// The blocks covered by the file, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_range: Option<RangeInclusive<u64>>,
+}
+
+impl ManifestEntry {
+    /// Hashes the file at `dir/path` and returns its entry.
+    pub fn new(
+        dir: &Path,
+        path: impl Into<String>,
+        block_range: Option<RangeInclusive<u64>>,
+    ) -> Result<Self, NippyJarError> {
+        let path = path.into();
+        let (size, sha256) = hash_file(&dir.join(&path))?;
+        Ok(Self { path, size, sha256, block_range })
+    }
+}
+
+/// Signature over the entries of a [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSignature {
+    /// Hex encoded compressed public key of the signer.
+    pub public_key: String,
+    /// Hex encoded compact ECDSA signature.
+    pub signature: String,
+}
+
+/// List of all files in a directory of jars, with their sizes, hashes and block ranges.
+///
+/// Entries are kept sorted by path, so the same directory always yields the same manifest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Version of the manifest format.
+    pub version: u32,
+    /// All files of the directory, sorted by path.
+    pub entries: Vec<ManifestEntry>,
+    /// Optional signature over version and entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+}
+
+impl Manifest {
+    /// Creates an empty manifest.
+    pub fn new() -> Self {
+        Self { version: MANIFEST_VERSION, entries: Vec::new(), signature: None }
+    }
+
+    /// Generates a manifest for all files in `dir`, including subdirectories.
+    ///
+    /// `block_range` is called with the relative path of each file and returns the blocks it
+    /// covers, if any.
+    pub fn generate(
+        dir: &Path,
+        block_range: impl Fn(&str) -> Option<RangeInclusive<u64>>,
+    ) -> Result<Self, NippyJarError> {
+        let mut manifest = Self::new();
+        for path in list_files(dir)? {
+            let range = block_range(&path);
+            manifest.entries.push(ManifestEntry::new(dir, path, range)?);
+        }
+        manifest.entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(manifest)
+    }
+
+    /// Loads the manifest from `dir`.
+    ///
+    /// Returns an error if any entry is not a plain relative path, see [`Self::verify`].
+    pub fn load(dir: &Path) -> Result<Self, NippyJarError> {
+        let file = File::open(dir.join(MANIFEST_FILE_NAME))?;
+        let manifest: Self = serde_json::from_reader(BufReader::new(file))?;
+        for entry in &manifest.entries {
+            check_entry_path(&entry.path)?;
+        }
+        Ok(manifest)
+    }
+
+    /// Applies `update` to the manifest of `dir` and saves it.
+    ///
+    /// Does nothing if `dir` has no manifest, so only directories that opted in by generating a
+    /// manifest are kept in sync. Returns whether a manifest was updated.
+    pub fn update(
+        dir: &Path,
+        update: impl FnOnce(&mut Self) -> Result<(), NippyJarError>,
+    ) -> Result<bool, NippyJarError> {
+        if !dir.join(MANIFEST_FILE_NAME).exists() {
+            return Ok(false)
+        }
+        let mut manifest = Self::load(dir)?;
+        update(&mut manifest)?;
+        manifest.save(dir)?;
+        Ok(true)
+    }
+
+    /// Writes the manifest to `dir`.
+    ///
+    /// The manifest is written to a temporary file first which then replaces the existing one, so
+    /// readers never observe a partially written manifest. Fails if the existing manifest can't be
+    /// replaced, e.g. because it is still in use on Windows.
+    pub fn save(&self, dir: &Path) -> Result<(), NippyJarError> {
+        let tmp = dir.join(MANIFEST_TMP_FILE_NAME);
+        let mut file = File::create(&tmp)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(tmp, dir.join(MANIFEST_FILE_NAME))?;
+        Ok(())
+    }
+
+    /// Returns the entry of the given relative path.
+    pub fn entry(&self, path: &str) -> Option<&ManifestEntry> {
+        self.entries
+            .binary_search_by(|entry| entry.path.as_str().cmp(path))
+            .ok()
+            .map(|idx| &self.entries[idx])
+    }
+
+    /// Inserts or replaces the entry of the file at `dir/path`.
+    ///
+    /// This invalidates the signature, so it is removed.
+    pub fn insert(
+        &mut self,
+        dir: &Path,
+        path: impl Into<String>,
+        block_range: Option<RangeInclusive<u64>>,
+    ) -> Result<(), NippyJarError> {
+        let entry = ManifestEntry::new(dir, path, block_range)?;
+        match self.entries.binary_search_by(|e| e.path.cmp(&entry.path)) {
+            Ok(idx) => self.entries[idx] = entry,
+            Err(idx) => self.entries.insert(idx, entry),
+        }
+        self.signature = None;
+        Ok(())
+    }
+
+    /// Inserts or replaces the entries of the data and index files of the jar.
+    ///
+    /// The jar is expected to be located in `dir`.
+    pub fn insert_jar<H>(
+        &mut self,
+        dir: &Path,
+        jar: &NippyJar<H>,
+        block_range: Option<RangeInclusive<u64>>,
+    ) -> Result<(), NippyJarError>
+    where
+        H: Send + Sync + Serialize + for<'a> Deserialize<'a>,
+    {
+        for path in [jar.data_path(), jar.index_path()] {
+            let path = relative_path(dir, &path)?;
+            self.insert(dir, path, block_range.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Removes the entries of the data and index files of the jar.
+    ///
+    /// The jar is expected to be located in `dir`.
+    pub fn remove_jar<H>(&mut self, dir: &Path, jar: &NippyJar<H>) -> Result<(), NippyJarError>
+    where
+        H: Send + Sync + Serialize + for<'a> Deserialize<'a>,
+    {
+        for path in [jar.data_path(), jar.index_path()] {
+            self.remove(&relative_path(dir, &path)?);
+        }
+        Ok(())
+    }
+
+    /// Removes the entry of the given relative path, e.g. after the file was pruned.
+    ///
+    /// Returns the removed entry and invalidates the signature, if the entry existed.
+    pub fn remove(&mut self, path: &str) -> Option<ManifestEntry> {
+        let idx = self.entries.binary_search_by(|entry| entry.path.as_str().cmp(path)).ok()?;
+        self.signature = None;
+        Some(self.entries.remove(idx))
+    }
+
+    /// Verifies that every listed file exists in `dir` with the listed size and hash.
+    ///
+    /// Files in `dir` that are not listed are ignored. Entries that are not plain relative paths,
+    /// e.g. absolute paths or paths with `..` components, are rejected, so a manifest from an
+    /// untrusted source can't reference files outside of `dir`.
+    pub fn verify(&self, dir: &Path) -> Result<(), NippyJarError> {
+        for entry in &self.entries {
+            check_entry_path(&entry.path)?;
+            let path = dir.join(&entry.path);
+            let (size, sha256) = match hash_file(&path) {
+                Ok(res) => res,
+                Err(NippyJarError::Disconnect(err)) if err.kind() == io::ErrorKind::NotFound => {
+                    return Err(NippyJarError::ManifestMissingFile(path))
+                }
+                Err(err) => return Err(err),
+            };
+            if size != entry.size || sha256 != entry.sha256 {
+                return Err(NippyJarError::ManifestMismatch(path))
+            }
+        }
+        Ok(())
+    }
+
+    /// Signs version and entries of the manifest with the given key.
+    pub fn sign(&mut self, secret_key: &SecretKey) {
+        let signature = SECP256K1.sign_ecdsa(&self.signing_message(), secret_key);
+        let public_key = PublicKey::from_secret_key(SECP256K1, secret_key);
+        self.signature = Some(ManifestSignature {
+            public_key: hex::encode(public_key.serialize()),
+            signature: hex::encode(signature.serialize_compact()),
+        });
+    }
+
+    /// Verifies that the manifest was signed by the given trusted key.
+    pub fn verify_signature(&self, trusted_key: &PublicKey) -> Result<(), NippyJarError> {
+        let signature = self.signature.as_ref().ok_or(NippyJarError::ManifestUnsigned)?;
+        let public_key = hex::decode(&signature.public_key)
+            .ok()
+            .and_then(|key| PublicKey::from_slice(&key).ok())
+            .ok_or(NippyJarError::ManifestInvalidSignature)?;
+        if public_key != *trusted_key {
+            return Err(NippyJarError::ManifestUntrustedSigner)
+        }
+        let sig = hex::decode(&signature.signature)
+            .ok()
+            .and_then(|sig| Signature::from_compact(&sig).ok())
+            .ok_or(NippyJarError::ManifestInvalidSignature)?;
+        SECP256K1
+            .verify_ecdsa(&self.signing_message(), &sig, &public_key)
+            .map_err(|_| NippyJarError::ManifestInvalidSignature)
+    }
+
+    /// Returns the SHA-256 digest of the serialized version and entries.
+    fn signing_message(&self) -> Message {
+        let payload =
+            serde_json::to_vec(&(self.version, &self.entries)).expect("manifest is serializable");
+        let digest: [u8; 32] = Sha256::digest(payload).into();
+        Message::from_slice(&digest).expect("digest is 32 bytes")
+    }
+}
+
+/// Returns the size and hex encoded SHA-256 hash of the file.
+fn hash_file(path: &Path) -> Result<(u64, String), NippyJarError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, hex::encode(hasher.finalize())))
+}
+
+/// Returns the relative paths of all files in `dir` and its subdirectories, except the manifest
+/// and its temporary file.
+fn list_files(dir: &Path) -> Result<Vec<String>, NippyJarError> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue
+            }
+            let relative = relative_path(dir, &path)?;
+            if relative != MANIFEST_FILE_NAME && relative != MANIFEST_TMP_FILE_NAME {
+                files.push(relative);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Returns `path` relative to `dir`, with `/` as separator on all platforms.
+pub(crate) fn relative_path(dir: &Path, path: &Path) -> Result<String, NippyJarError> {
+    let relative: PathBuf = path
+        .strip_prefix(dir)
+        .map_err(|_| NippyJarError::ManifestOutsideDirectory(path.to_path_buf()))?
+        .to_path_buf();
+    Ok(relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Returns an error unless `path` is a plain relative path, i.e. `/` separated file and directory
+/// names without `.` or `..` components, root or drive prefixes.
+fn check_entry_path(path: &str) -> Result<(), NippyJarError> {
+    let is_plain = !path.is_empty() &&
+        path.split('/').all(|name| {
+            !name.is_empty() && name != "." && name != ".." && !name.contains(['\\', ':'])
+        });
+    if !is_plain {
+        return Err(NippyJarError::ManifestInvalidPath(path.to_string()))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_files(dir: &Path) {
+        std::fs::write(dir.join("headers_0_499"), b"headers").unwrap();
+        std::fs::create_dir(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub").join("receipts_0_499"), b"receipts").unwrap();
+    }
+
+    #[test]
+    fn generate_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path());
+
+        let manifest = Manifest::generate(dir.path(), |_| Some(0..=499)).unwrap();
+        assert_eq!(
+            manifest.entries.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(),
+            vec!["headers_0_499", "sub/receipts_0_499"]
+        );
+        let entry = manifest.entry("headers_0_499").unwrap();
+        assert_eq!(entry.size, 7);
+        assert_eq!(entry.block_range, Some(0..=499));
+        manifest.verify(dir.path()).unwrap();
+
+        manifest.save(dir.path()).unwrap();
+        let loaded = Manifest::load(dir.path()).unwrap();
+        assert_eq!(loaded, manifest);
+        // the manifest file itself is not listed
+        assert_eq!(Manifest::generate(dir.path(), |_| None).unwrap().entries.len(), 2);
+
+        std::fs::write(dir.path().join("headers_0_499"), b"tampered").unwrap();
+        assert!(matches!(manifest.verify(dir.path()), Err(NippyJarError::ManifestMismatch(_))));
+
+        std::fs::remove_file(dir.path().join("headers_0_499")).unwrap();
+        assert!(matches!(manifest.verify(dir.path()), Err(NippyJarError::ManifestMissingFile(_))));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path());
+
+        let mut manifest = Manifest::new();
+        manifest.insert(dir.path(), "sub/receipts_0_499", Some(0..=499)).unwrap();
+        manifest.insert(dir.path(), "headers_0_499", Some(0..=499)).unwrap();
+        assert_eq!(manifest, Manifest::generate(dir.path(), |_| Some(0..=499)).unwrap());
+
+        assert!(manifest.remove("headers_0_499").is_some());
+        assert!(manifest.remove("headers_0_499").is_none());
+        assert_eq!(manifest.entries.len(), 1);
+    }
+
+    #[test]
+    fn reject_paths_outside_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let jars = dir.path().join("jars");
+        std::fs::create_dir(&jars).unwrap();
+        write_files(&jars);
+        std::fs::write(dir.path().join("secret"), b"secret").unwrap();
+
+        for path in ["../secret", "/etc/passwd", "sub/../../secret", "./headers_0_499", "", "a//b"]
+        {
+            let mut manifest = Manifest::generate(&jars, |_| None).unwrap();
+            manifest.entries[0].path = path.to_string();
+            assert!(matches!(
+                manifest.verify(&jars),
+                Err(NippyJarError::ManifestInvalidPath(p)) if p == path
+            ));
+
+            manifest.save(&jars).unwrap();
+            assert!(matches!(
+                Manifest::load(&jars),
+                Err(NippyJarError::ManifestInvalidPath(p)) if p == path
+            ));
+        }
+        assert!(matches!(
+            check_entry_path("C:\\secret"),
+            Err(NippyJarError::ManifestInvalidPath(_))
+        ));
+        check_entry_path("sub/receipts_0_499").unwrap();
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path());
+
+        let secret_key = SecretKey::from_slice(&[0xcd; 32]).unwrap();
+        let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key);
+        let other_key =
+            PublicKey::from_secret_key(SECP256K1, &SecretKey::from_slice(&[0xab; 32]).unwrap());
+
+        let mut manifest = Manifest::generate(dir.path(), |_| None).unwrap();
+        assert!(matches!(
+            manifest.verify_signature(&public_key),
+            Err(NippyJarError::ManifestUnsigned)
+        ));
+
+        manifest.sign(&secret_key);
+        manifest.verify_signature(&public_key).unwrap();
+        assert!(matches!(
+            manifest.verify_signature(&other_key),
+            Err(NippyJarError::ManifestUntrustedSigner)
+        ));
+
+        // tampering with an entry invalidates the signature
+        manifest.entries[0].size += 1;
+        assert!(matches!(
+            manifest.verify_signature(&public_key),
+            Err(NippyJarError::ManifestInvalidSignature)
+        ));
+
+        // modifying the manifest drops the signature
+        manifest.sign(&secret_key);
+        manifest.insert(dir.path(), "headers_0_499", None).unwrap();
+        assert!(manifest.signature.is_none());
+    }
+}
@@ -406,16 +406,24 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
-    use crate::{BlockHashReader, BlockNumReader, BlockWriter, TransactionsProvider};
+    use crate::{
+        BlockHashReader, BlockNumReader, BlockReader, BlockWriter, PruneCheckpointWriter,
+        ReceiptProvider, TransactionsProvider,
+    };
     use assert_matches::assert_matches;
     use reth_db::{
         tables,
         test_utils::{create_test_rw_db, ERROR_TEMPDIR},
         DatabaseEnv,
     };
-    use reth_interfaces::test_utils::{generators, generators::random_block};
+    use reth_interfaces::{
+        provider::ProviderError,
+        test_utils::{generators, generators::random_block},
+        RethError,
+    };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, TxNumber, H256,
+        hex_literal::hex, ChainSpecBuilder, PruneCheckpoint, PruneMode, PruneModes, PrunePart,
+        SealedBlock, TxNumber, H256,
     };
     use reth_rlp::Decodable;
     use std::{ops::RangeInclusive, sync::Arc};
@@ -507,6 +515,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pruned_senders_and_receipts_unavailable() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_rw_db();
+        let factory = ProviderFactory::new(db, Arc::new(chain_spec));
+
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), None);
+
+        let provider = factory.provider_rw().unwrap();
+        let prune_modes =
+            PruneModes { sender_recovery: Some(PruneMode::Full), ..PruneModes::none() };
+        assert_matches!(provider.insert_block(block.clone(), None, Some(&prune_modes)), Ok(_));
+
+        for part in [PrunePart::SenderRecovery, PrunePart::Receipts] {
+            provider
+                .save_prune_checkpoint(
+                    part,
+                    PruneCheckpoint {
+                        block_number: Some(block.number),
+                        tx_number: Some(2),
+                        prune_mode: PruneMode::Full,
+                    },
+                )
+                .unwrap();
+        }
+
+        assert_matches!(
+            provider.block_with_senders(block.number),
+            Err(RethError::Provider(ProviderError::HistoryUnavailable {
+                part: PrunePart::SenderRecovery,
+                block_number: 1,
+                lowest_available: 2,
+            }))
+        );
+        assert_matches!(
+            provider.receipts_by_block(block.number.into()),
+            Err(RethError::Provider(ProviderError::HistoryUnavailable {
+                part: PrunePart::Receipts,
+                block_number: 1,
+                lowest_available: 2,
+            }))
+        );
+    }
+
     #[test]
     fn get_take_block_transaction_range_recover_senders() {
         let chain_spec = ChainSpecBuilder::mainnet().build();
@@ -187,6 +187,22 @@ impl<'this, TX: DbTx<'this>> DatabaseProvider<'this, TX> {
             .walk(Some(T::Key::default()))?
             .collect::<std::result::Result<Vec<_>, DatabaseError>>()
     }
+
+    /// Returns [ProviderError::HistoryUnavailable] if the given part was pruned at the given
+    /// block.
+    fn ensure_not_pruned(&self, part: PrunePart, block_number: BlockNumber) -> RethResult<()> {
+        let pruned =
+            self.get_prune_checkpoint(part)?.and_then(|checkpoint| checkpoint.block_number);
+        match pruned {
+            Some(pruned) if block_number <= pruned => Err(ProviderError::HistoryUnavailable {
+                part,
+                block_number,
+                lowest_available: pruned + 1,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl<'this, TX: DbTxMut<'this> + DbTx<'this>> DatabaseProvider<'this, TX> {
@@ -1041,6 +1057,7 @@ impl<'this, TX: DbTx<'this>> BlockReader for DatabaseProvider<'this, TX> {
     /// If the header for this block is not found, this returns `None`.
     /// If the header is found, but the transactions either do not exist, or are not indexed, this
     /// will return None.
+    /// If the senders of the block were pruned, this returns [ProviderError::HistoryUnavailable].
     fn block_with_senders(
         &self,
         block_number: BlockNumber,
@@ -1071,6 +1088,12 @@ impl<'this, TX: DbTx<'this>> BlockReader for DatabaseProvider<'this, TX> {
             (self.transactions_by_tx_range(tx_range.clone())?, self.senders_by_tx_range(tx_range)?)
         };
 
+        // senders below the prune checkpoint are gone, the block can only be returned without them
+        if senders.len() != transactions.len() {
+            self.ensure_not_pruned(PrunePart::SenderRecovery, block_number)?;
+            return Ok(None)
+        }
+
         let body = transactions
             .into_iter()
             .map(|tx| {
@@ -1257,6 +1280,7 @@ impl<'this, TX: DbTx<'this>> ReceiptProvider for DatabaseProvider<'this, TX> {
                 return if tx_range.is_empty() {
                     Ok(Some(Vec::new()))
                 } else {
+                    self.ensure_not_pruned(PrunePart::Receipts, number)?;
                     let mut receipts_cursor = self.tx.cursor_read::<tables::Receipts>()?;
                     let receipts = receipts_cursor
                         .walk_range(tx_range)?
@@ -10,9 +10,9 @@ use reth_db::{
     transaction::DbTx,
     BlockNumberList,
 };
-use reth_interfaces::RethResult;
+use reth_interfaces::{RethError, RethResult};
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, H256,
+    Account, Address, BlockNumber, Bytecode, Bytes, PrunePart, StorageKey, StorageValue, H256,
 };
 use std::marker::PhantomData;
 
@@ -68,10 +68,27 @@ impl<'a, 'b, TX: DbTx<'a>> HistoricalStateProviderRef<'a, 'b, TX> {
         Self { tx, block_number, lowest_available_blocks, _phantom: PhantomData {} }
     }
 
+    /// Returns the [ProviderError::HistoryUnavailable] error for the given prune part.
+    fn history_unavailable(
+        &self,
+        part: PrunePart,
+        lowest_available: Option<BlockNumber>,
+    ) -> RethError {
+        ProviderError::HistoryUnavailable {
+            part,
+            block_number: self.block_number,
+            lowest_available: lowest_available.unwrap_or_default(),
+        }
+        .into()
+    }
+
     /// Lookup an account in the AccountHistory table
     pub fn account_history_lookup(&self, address: Address) -> RethResult<HistoryInfo> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number).into())
+            return Err(self.history_unavailable(
+                PrunePart::AccountHistory,
+                self.lowest_available_blocks.account_history_block_number,
+            ))
         }
 
         // history key to search IntegerList of block number changesets.
@@ -90,7 +107,10 @@ impl<'a, 'b, TX: DbTx<'a>> HistoricalStateProviderRef<'a, 'b, TX> {
         storage_key: StorageKey,
     ) -> RethResult<HistoryInfo> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number).into())
+            return Err(self.history_unavailable(
+                PrunePart::StorageHistory,
+                self.lowest_available_blocks.storage_history_block_number,
+            ))
         }
 
         // history key to search IntegerList of block number changesets.
@@ -357,7 +377,7 @@ mod tests {
         BlockNumberList,
     };
     use reth_interfaces::provider::ProviderError;
-    use reth_primitives::{hex_literal::hex, Account, StorageEntry, H160, H256, U256};
+    use reth_primitives::{hex_literal::hex, Account, PrunePart, StorageEntry, H160, H256, U256};
 
     const ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000001"));
     const HIGHER_ADDRESS: H160 = H160(hex!("0000000000000000000000000000000000000005"));
@@ -586,11 +606,21 @@ mod tests {
         );
         assert_eq!(
             provider.account_history_lookup(ADDRESS),
-            Err(ProviderError::StateAtBlockPruned(provider.block_number).into())
+            Err(ProviderError::HistoryUnavailable {
+                part: PrunePart::AccountHistory,
+                block_number: provider.block_number,
+                lowest_available: 3,
+            }
+            .into())
         );
         assert_eq!(
             provider.storage_history_lookup(ADDRESS, STORAGE),
-            Err(ProviderError::StateAtBlockPruned(provider.block_number).into())
+            Err(ProviderError::HistoryUnavailable {
+                part: PrunePart::StorageHistory,
+                block_number: provider.block_number,
+                lowest_available: 3,
+            }
+            .into())
         );
 
         // provider block_number == lowest available block number,
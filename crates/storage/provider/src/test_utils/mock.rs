@@ -3,17 +3,17 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BundleStateDataProvider, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionsProvider, WithdrawalsProvider,
+    PruneCheckpointReader, ReceiptProviderIdExt, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::StoredBlockBodyIndices;
 use reth_interfaces::{provider::ProviderError, RethResult};
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt, SealedBlock,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, H256, U256,
+    BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, PruneCheckpoint, PrunePart,
+    Receipt, SealedBlock, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, H256, U256,
 };
 use reth_revm_primitives::primitives::{BlockEnv, CfgEnv};
 use std::{
@@ -437,8 +437,10 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
-    fn block_with_senders(&self, _number: BlockNumber) -> RethResult<Option<BlockWithSenders>> {
-        Ok(None)
+    fn block_with_senders(&self, number: BlockNumber) -> RethResult<Option<BlockWithSenders>> {
+        Ok(self
+            .block(number.into())?
+            .and_then(|block| block.senders().map(|senders| block.with_senders(senders))))
     }
 }
 
@@ -634,3 +636,9 @@ impl WithdrawalsProvider for MockEthProvider {
         unimplemented!()
     }
 }
+
+impl PruneCheckpointReader for MockEthProvider {
+    fn get_prune_checkpoint(&self, _part: PrunePart) -> RethResult<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+}
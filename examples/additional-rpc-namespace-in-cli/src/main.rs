@@ -22,7 +22,7 @@ use reth::{
     network::{NetworkInfo, Peers},
     providers::{
         BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-        EvmEnvProvider, StateProviderFactory,
+        EvmEnvProvider, PruneCheckpointReader, StateProviderFactory,
     },
     rpc::builder::{RethModuleRegistry, TransportRpcModules},
     tasks::TaskSpawner,
@@ -64,6 +64,7 @@ impl RethNodeCommandConfig for RethCliTxpoolExt {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,